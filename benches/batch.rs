@@ -0,0 +1,73 @@
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use sugar_path::{
+  SugarPath,
+  rayon::{normalize_all, relative_all, to_slash_all},
+};
+
+mod support;
+
+use support::workloads::{DIRTY_PATHS, PathCase, ROLLDOWN_PATHS, ROLLDOWN_ROOT};
+
+/// Module-graph size, matching the 12,287 tracked paths of the sampled
+/// Rolldown tree.
+const GRAPH_LEN: usize = 12_288;
+
+/// Distinct paths built by nesting each case under a numbered directory.
+fn module_graph(cases: &[PathCase]) -> Vec<PathBuf> {
+  (0..GRAPH_LEN)
+    .map(|index| {
+      let path = PathBuf::from(cases[index % cases.len()].path);
+      let file_name = path.file_name().expect("workload paths end in a file name").to_owned();
+      path.with_file_name(index.to_string()).join(file_name)
+    })
+    .collect()
+}
+
+fn bench_batch(criterion: &mut Criterion) {
+  let clean = module_graph(ROLLDOWN_PATHS);
+  let dirty = module_graph(DIRTY_PATHS);
+
+  for (name, graph) in [("rolldown_graph", &clean), ("dirty_graph", &dirty)] {
+    let mut group = criterion.benchmark_group(format!("batch/normalize/{name}"));
+    group.throughput(Throughput::Elements(graph.len() as u64));
+    group.bench_function("sequential", |bencher| {
+      bencher.iter(|| {
+        black_box(black_box(graph).iter().map(|path| path.normalize()).collect::<Vec<_>>())
+      });
+    });
+    group.bench_function("rayon", |bencher| {
+      bencher.iter(|| black_box(normalize_all(black_box(graph))));
+    });
+    group.finish();
+  }
+
+  let mut group = criterion.benchmark_group("batch/relative/rolldown_graph");
+  group.throughput(Throughput::Elements(clean.len() as u64));
+  group.bench_function("sequential", |bencher| {
+    bencher.iter(|| {
+      let base = black_box(ROLLDOWN_ROOT);
+      black_box(black_box(&clean).iter().map(|path| path.relative(base)).collect::<Vec<_>>())
+    });
+  });
+  group.bench_function("rayon", |bencher| {
+    bencher.iter(|| black_box(relative_all(black_box(&clean), black_box(ROLLDOWN_ROOT))));
+  });
+  group.finish();
+
+  let mut group = criterion.benchmark_group("batch/to_slash/rolldown_graph");
+  group.throughput(Throughput::Elements(clean.len() as u64));
+  group.bench_function("sequential", |bencher| {
+    bencher
+      .iter(|| black_box(black_box(&clean).iter().map(|path| path.to_slash()).collect::<Vec<_>>()));
+  });
+  group.bench_function("rayon", |bencher| {
+    bencher.iter(|| black_box(to_slash_all(black_box(&clean))));
+  });
+  group.finish();
+}
+
+criterion_group!(benches, bench_batch);
+criterion_main!(benches);
@@ -31,6 +31,27 @@ fn bench_hot_path_baselines(criterion: &mut Criterion) {
   }
   group.finish();
 
+  // Clean inputs scan every separator; the dotfile input adds separators
+  // followed by `.` that the vector scans must reject one by one.
+  #[cfg(not(target_family = "windows"))]
+  let (clean, dotfiles) = (
+    "/workspace/rolldown/crates/rolldown/tests/rolldown/function/chunk_optimization/main2.js",
+    "/workspace/rolldown/.github/.cache/rolldown/.config/chunk_optimization/.output/main2.js",
+  );
+  #[cfg(target_family = "windows")]
+  let (clean, dotfiles) = (
+    r"C:\workspace\rolldown\crates\rolldown\tests\rolldown\function\chunk_optimization\main2.js",
+    r"C:\workspace\rolldown\.github\.cache\rolldown\.config\chunk_optimization\.output\main2.js",
+  );
+  let mut group = criterion.benchmark_group("normalize/separator_scan/valid");
+  for (name, path) in [("clean", clean), ("dotfiles", dotfiles)] {
+    group.throughput(Throughput::Bytes(path.len() as u64));
+    group.bench_function(name, |bencher| {
+      bencher.iter(|| black_box(Path::new(black_box(path)).normalize()));
+    });
+  }
+  group.finish();
+
   #[cfg(any(unix, windows))]
   {
     let non_utf8 = invalid_unicode_path();
@@ -73,6 +94,20 @@ fn bench_hot_path_baselines(criterion: &mut Criterion) {
       bencher.iter(|| black_box(black_box(case.target).relative(black_box(case.base))));
     });
     group.finish();
+
+    // The common-prefix scan dominates when the shared prefix spans several
+    // vector blocks.
+    let mut group = criterion.benchmark_group("relative/common_prefix");
+    for case in RELATIVE_CASES.iter().filter(|case| {
+      matches!(case.name, "short_common_prefix" | "same_directory" | "deep_siblings")
+    }) {
+      group.throughput(Throughput::Bytes((case.target.len() + case.base.len()) as u64));
+      group.bench_function(case.name, |bencher| {
+        bencher
+          .iter(|| black_box(Path::new(black_box(case.target)).relative(black_box(case.base))));
+      });
+    }
+    group.finish();
   }
 }
 
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use arcstr::ArcStr;
 use criterion::{Criterion, Throughput, criterion_group, criterion_main};
-use sugar_path::{SugarPath, SugarPathBuf};
+use sugar_path::{SugarCow, SugarPath, SugarPathBuf};
 
 mod support;
 
@@ -105,6 +105,22 @@ fn bench_rolldown_pipelines(criterion: &mut Criterion) {
     });
   });
 
+  group.bench_function("descendant/cow_receiver/string_result", |bencher| {
+    bencher.iter(|| {
+      let target = Path::new(black_box(case.target));
+      let base = Path::new(black_box(case.base));
+      black_box(target.relative(base).into_slash().into_owned())
+    });
+  });
+
+  group.bench_function("descendant/cow_receiver/normalized_string_result", |bencher| {
+    bencher.iter(|| {
+      let target = Path::new(black_box(case.target));
+      let base = Path::new(black_box(case.base));
+      black_box(target.relative(base).into_normalized().into_slash().into_owned())
+    });
+  });
+
   group.throughput(Throughput::Bytes(
     (leading_parent_case.target.len() + leading_parent_case.base.len()) as u64,
   ));
@@ -143,6 +159,22 @@ fn bench_rolldown_pipelines(criterion: &mut Criterion) {
     });
   });
 
+  group.bench_function("upward/cow_receiver/string_result", |bencher| {
+    bencher.iter(|| {
+      let target = Path::new(black_box(leading_parent_case.target));
+      let base = Path::new(black_box(leading_parent_case.base));
+      black_box(target.relative(base).into_slash().into_owned())
+    });
+  });
+
+  group.bench_function("upward/cow_receiver/normalized_string_result", |bencher| {
+    bencher.iter(|| {
+      let target = Path::new(black_box(leading_parent_case.target));
+      let base = Path::new(black_box(leading_parent_case.base));
+      black_box(target.relative(base).into_normalized().into_slash().into_owned())
+    });
+  });
+
   group.finish();
 
   let mut group = criterion.benchmark_group("rolldown/relative_arcstr_result");
@@ -0,0 +1,457 @@
+//! Lexical algorithms over raw path bytes in an explicit syntax.
+//!
+//! Use this module for paths that are not a native [`Path`] yet, such as
+//! entry names read from tar or zip headers, or paths written in another
+//! platform's syntax. [`normalize`] applies the rules of
+//! [`SugarPath::normalize`] to the bytes directly, on every host, and returns
+//! the bytes that `normalize` produces on a host of the chosen [`Syntax`].
+//! [`relative_with`] and [`to_slash`] do the same for
+//! [`SugarPath::relative_with`] and [`SugarPath::to_slash`].
+//!
+//! This module only needs `alloc`. It is the whole public API when the crate
+//! is built without its default `std` feature.
+//!
+//! Components are copied unchanged, so arbitrary non-UTF-8 bytes are
+//! preserved. Only the ASCII separators and `.` and `..` components are
+//! interpreted.
+//!
+//! # Examples
+//!
+//! ```
+//! use sugar_path::bytes::{Syntax, normalize};
+//!
+//! assert_eq!(&*normalize(b"pkg/./lib/../bin/\xFFtool", Syntax::Unix), b"pkg/bin/\xFFtool");
+//! assert_eq!(&*normalize(b"C:/work/./src/..", Syntax::Windows), br"C:\work");
+//! ```
+//!
+//! [`Path`]: std::path::Path
+//! [`SugarPath::normalize`]: crate::SugarPath::normalize
+//! [`SugarPath::relative_with`]: crate::SugarPath::relative_with
+//! [`SugarPath::to_slash`]: crate::SugarPath::to_slash
+
+use alloc::{borrow::Cow, vec, vec::Vec};
+use core::ops::Range;
+
+use memchr::memchr;
+use smallvec::SmallVec;
+
+use crate::lexical::{
+  LexicalComponent, TrailingSeparator, push_lexical_components, unix_bytes_need_normalization,
+  windows_bytes_need_normalization, windows_standalone_relative_bytes_are_representable,
+};
+
+/// The path syntax used to interpret bytes passed to this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Syntax {
+  /// `/` is the only separator and a leading `/` is the root, as on Unix,
+  /// WASI, and in tar and zip entry names.
+  Unix,
+  /// `\` and `/` are separators and `\` is written. Drive, UNC, device, and
+  /// verbatim prefixes are recognized as Rust recognizes them on Windows, and
+  /// `/` is a literal character after a verbatim prefix.
+  Windows,
+}
+
+impl Syntax {
+  /// The syntax of the host's [`Path`](std::path::Path).
+  pub const NATIVE: Self = if cfg!(target_family = "windows") { Self::Windows } else { Self::Unix };
+}
+
+/// Lexically normalizes `path` in `syntax`.
+///
+/// This removes `.` components and redundant separators, resolves `..`
+/// against preceding normal components, and prevents a rooted path from
+/// ascending above its root. An empty path normalizes to `.`, and one trailing
+/// separator is preserved. For [`Syntax::NATIVE`], the result equals the
+/// encoded bytes of [`SugarPath::normalize`](crate::SugarPath::normalize).
+///
+/// An already-normalized `path` is returned borrowed. A current-directory
+/// result may borrow the static `.`; other results are owned.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use sugar_path::bytes::{Syntax, normalize};
+///
+/// assert_eq!(&*normalize(b"../a/b/../../c/", Syntax::Unix), b"../c/");
+/// assert!(matches!(normalize(b"a/b", Syntax::Unix), Cow::Borrowed(_)));
+///
+/// assert_eq!(&*normalize(br"\\server\share\a\..\b", Syntax::Windows), br"\\server\share\b");
+/// assert_eq!(&*normalize(b"C:", Syntax::Windows), b"C:.");
+/// ```
+pub fn normalize(path: &[u8], syntax: Syntax) -> Cow<'_, [u8]> {
+  match syntax {
+    Syntax::Unix if unix_bytes_need_normalization(path, b'/', TrailingSeparator::Preserve) => {
+      normalize_unix(path)
+    }
+    Syntax::Windows if windows_bytes_need_normalization(path, TrailingSeparator::Preserve) => {
+      normalize_windows(path)
+    }
+    Syntax::Unix | Syntax::Windows => Cow::Borrowed(path),
+  }
+}
+
+/// Returns the lexical path from `base` to `target` in [`Syntax::Unix`], using
+/// `cwd` as the current directory for relative inputs.
+///
+/// Both inputs are normalized first, so `.` components, redundant separators,
+/// trailing separators, and resolvable `..` components do not affect the
+/// result. Equal paths produce an empty result. If the result is independent of
+/// `cwd`, `cwd` is neither inspected nor validated. For Unix hosts, the result
+/// equals the encoded bytes of
+/// [`SugarPath::relative_with`](crate::SugarPath::relative_with).
+///
+/// A result that is a contiguous part of `target` is returned borrowed. The
+/// result never borrows from `base` or `cwd`.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use sugar_path::bytes::relative_with;
+///
+/// assert_eq!(&*relative_with(b"/srv/app/bin", b"/srv/lib", b"/"), b"../app/bin");
+/// assert_eq!(&*relative_with(b"src/lib.rs", b"src", b"/workspace"), b"lib.rs");
+/// assert_eq!(&*relative_with(b"lib.rs", b"/workspace", b"/workspace/src"), b"src/lib.rs");
+/// assert!(matches!(relative_with(b"/srv/app/bin", b"/srv", b"/"), Cow::Borrowed(b"app/bin")));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the calculation needs `cwd` and `cwd` is not absolute.
+pub fn relative_with<'a>(target: &'a [u8], base: &[u8], cwd: &[u8]) -> Cow<'a, [u8]> {
+  let mut target_stack = ComponentStack::new();
+  let mut base_stack = ComponentStack::new();
+  let target_parents = push_unix_components(&mut target_stack, target);
+  let base_parents = push_unix_components(&mut base_stack, base);
+
+  // Absolute inputs, and relative inputs that climb out of the same cwd
+  // ancestor, relate to each other without cwd.
+  let target_is_absolute = target.first() == Some(&b'/');
+  let base_is_absolute = base.first() == Some(&b'/');
+  let cwd_independent = match (target_is_absolute, base_is_absolute) {
+    (true, true) => true,
+    (false, false) => target_parents == base_parents,
+    _ => false,
+  };
+  if !cwd_independent {
+    assert!(cwd.first() == Some(&b'/'), "explicit current directory must be absolute");
+    if !target_is_absolute {
+      target_stack = resolve_against(cwd, target);
+    }
+    if !base_is_absolute {
+      base_stack = resolve_against(cwd, base);
+    }
+  }
+
+  let common_len = base_stack.iter().zip(&target_stack).take_while(|(from, to)| from == to).count();
+  let up_len = base_stack.len() - common_len;
+  let target_suffix = &target_stack[common_len..];
+  if up_len == 0
+    && let Some(range) = contiguous_range(target, target_suffix)
+  {
+    return Cow::Borrowed(&target[range]);
+  }
+
+  let component_count = up_len + target_suffix.len();
+  let capacity = up_len * 2
+    + target_suffix.iter().map(|component| component.len()).sum::<usize>()
+    + component_count.saturating_sub(1);
+  let mut relative = Vec::with_capacity(capacity);
+  for component in core::iter::repeat_n(&b".."[..], up_len).chain(target_suffix.iter().copied()) {
+    if !relative.is_empty() {
+      relative.push(b'/');
+    }
+    relative.extend_from_slice(component);
+  }
+  Cow::Owned(relative)
+}
+
+/// Converts the separators of `path` in `syntax` to `/`.
+///
+/// This does not normalize components, and bytes other than separators are
+/// copied unchanged. [`Syntax::Unix`] input is always returned borrowed, as is
+/// [`Syntax::Windows`] input without a `\`. For [`Syntax::NATIVE`], the result
+/// equals the bytes of [`SugarPath::to_slash`](crate::SugarPath::to_slash) for
+/// valid UTF-8 input.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::bytes::{Syntax, to_slash};
+///
+/// assert_eq!(&*to_slash(br"src\lib.rs", Syntax::Windows), b"src/lib.rs");
+/// assert_eq!(&*to_slash(br"src\lib.rs", Syntax::Unix), br"src\lib.rs");
+/// ```
+pub fn to_slash(path: &[u8], syntax: Syntax) -> Cow<'_, [u8]> {
+  match syntax {
+    Syntax::Windows if memchr(b'\\', path).is_some() => {
+      Cow::Owned(path.iter().map(|&byte| if byte == b'\\' { b'/' } else { byte }).collect())
+    }
+    Syntax::Unix | Syntax::Windows => Cow::Borrowed(path),
+  }
+}
+
+fn normalize_unix(path: &[u8]) -> Cow<'_, [u8]> {
+  let mut buf = Vec::with_capacity(path.len());
+  let has_root = path.first() == Some(&b'/');
+  if has_root {
+    buf.push(b'/');
+  }
+  let root_end = buf.len();
+  push_lexical_components(
+    &mut buf,
+    root_end,
+    has_root,
+    b'/',
+    components(path, |byte| byte == b'/'),
+  );
+  finish(buf, b'/', path.last() == Some(&b'/'))
+}
+
+fn normalize_windows(path: &[u8]) -> Cow<'_, [u8]> {
+  let prefix = parse_windows_prefix(path);
+  let verbatim = prefix.as_ref().is_some_and(|prefix| prefix.kind.is_verbatim());
+  let is_separator = move |byte: u8| byte == b'\\' || (byte == b'/' && !verbatim);
+
+  let mut buf = Vec::with_capacity(path.len());
+  let rest = match &prefix {
+    Some(prefix) => {
+      prefix.write(path, &mut buf);
+      &path[prefix.len..]
+    }
+    None => path,
+  };
+  let prefix_len = buf.len();
+  let kind = prefix.map(|prefix| prefix.kind);
+
+  // Rust reports a root after UNC and device prefixes even without a
+  // separator. A UNC root is written; a device root stays implicit.
+  let physical_root = rest.first().is_some_and(|&byte| is_separator(byte));
+  let implicit_root = matches!(kind, Some(PrefixKind::Unc { .. } | PrefixKind::DeviceNs));
+  let root_is_optional = kind.is_some_and(PrefixKind::root_is_optional);
+  if physical_root || (implicit_root && !root_is_optional) {
+    buf.push(b'\\');
+  }
+  let has_root = physical_root || implicit_root;
+
+  let root_end = buf.len();
+  let depth =
+    push_lexical_components(&mut buf, root_end, has_root, b'\\', components(rest, is_separator));
+
+  let preserve_trailing = path.last().is_some_and(|&byte| is_separator(byte));
+  if root_is_optional && depth == 0 && !preserve_trailing {
+    buf.truncate(prefix_len);
+  }
+  // Keep a normal component such as `C:foo` from becoming a drive prefix.
+  if prefix_len == 0 && !has_root && !windows_standalone_relative_bytes_are_representable(&buf) {
+    buf.splice(0..0, *b".\\");
+  }
+  if prefix_len > 0
+    && buf.len() == prefix_len
+    && let Some(suffix) = kind.and_then(PrefixKind::prefix_only_suffix)
+  {
+    buf.push(suffix);
+  }
+  finish(buf, b'\\', preserve_trailing)
+}
+
+/// Splits the part of a path after its prefix and root into components.
+fn components(
+  path: &[u8],
+  is_separator: impl Fn(u8) -> bool,
+) -> impl Iterator<Item = LexicalComponent<'_>> {
+  path.split(move |&byte| is_separator(byte)).map(|component| match component {
+    b"" | b"." => LexicalComponent::CurDir,
+    b".." => LexicalComponent::ParentDir,
+    normal => LexicalComponent::Normal(normal),
+  })
+}
+
+type ComponentStack<'a> = SmallVec<[&'a [u8]; 16]>;
+
+/// Pushes the normal components of a Unix-syntax `path` onto `stack`, popping
+/// one for each `..`. Returns the number of `..` components that found the
+/// stack empty; a rooted path ignores them.
+fn push_unix_components<'a>(stack: &mut ComponentStack<'a>, path: &'a [u8]) -> usize {
+  let mut unresolved_parents = 0;
+  for component in components(path, |byte| byte == b'/') {
+    match component {
+      LexicalComponent::CurDir => {}
+      LexicalComponent::ParentDir => {
+        if stack.pop().is_none() {
+          unresolved_parents += 1;
+        }
+      }
+      LexicalComponent::Normal(normal) => stack.push(normal),
+    }
+  }
+  unresolved_parents
+}
+
+fn resolve_against<'a>(cwd: &'a [u8], path: &'a [u8]) -> ComponentStack<'a> {
+  let mut stack = ComponentStack::new();
+  push_unix_components(&mut stack, cwd);
+  push_unix_components(&mut stack, path);
+  stack
+}
+
+/// Returns the range of `path` spelling `components` joined by single
+/// separators, if they are consecutive slices of `path`.
+fn contiguous_range(path: &[u8], components: &[&[u8]]) -> Option<Range<usize>> {
+  let (Some(first), Some(last)) = (components.first(), components.last()) else {
+    return Some(0..0);
+  };
+  let start = offset_in(path, first)?;
+  let end = offset_in(path, last)? + last.len();
+  let joined_len =
+    components.iter().map(|component| component.len()).sum::<usize>() + components.len() - 1;
+  (end - start == joined_len).then_some(start..end)
+}
+
+fn offset_in(path: &[u8], component: &[u8]) -> Option<usize> {
+  let offset = (component.as_ptr() as usize).checked_sub(path.as_ptr() as usize)?;
+  (offset + component.len() <= path.len()).then_some(offset)
+}
+
+fn finish(mut buf: Vec<u8>, separator: u8, preserve_trailing: bool) -> Cow<'static, [u8]> {
+  if buf.is_empty() {
+    if preserve_trailing {
+      return Cow::Owned(vec![b'.', separator]);
+    }
+    return Cow::Borrowed(b".");
+  }
+  if preserve_trailing && buf.last() != Some(&separator) {
+    buf.push(separator);
+  }
+  Cow::Owned(buf)
+}
+
+#[derive(Clone, Copy)]
+enum PrefixKind {
+  /// `\\?\prefix`
+  Verbatim,
+  /// `\\?\UNC\server\share`
+  VerbatimUnc,
+  /// `\\?\C:`
+  VerbatimDisk,
+  /// `\\.\device`
+  DeviceNs,
+  /// `\\server\share`, with the separator between them at `separator`.
+  Unc { separator: usize },
+  /// `C:`
+  Disk,
+}
+
+impl PrefixKind {
+  fn is_verbatim(self) -> bool {
+    matches!(self, Self::Verbatim | Self::VerbatimUnc | Self::VerbatimDisk)
+  }
+
+  /// Whether a root after the prefix is dropped when nothing follows it.
+  fn root_is_optional(self) -> bool {
+    matches!(self, Self::Verbatim | Self::VerbatimUnc | Self::DeviceNs)
+  }
+
+  /// The byte that keeps a prefix-only result from changing meaning.
+  fn prefix_only_suffix(self) -> Option<u8> {
+    match self {
+      Self::Disk => Some(b'.'),
+      Self::Unc { .. } => Some(b'\\'),
+      _ => None,
+    }
+  }
+}
+
+struct WindowsPrefix {
+  kind: PrefixKind,
+  len: usize,
+}
+
+impl WindowsPrefix {
+  /// Writes the prefix with `\` in place of any `/` Rust accepted in it.
+  fn write(&self, path: &[u8], buf: &mut Vec<u8>) {
+    let prefix = &path[..self.len];
+    match self.kind {
+      PrefixKind::DeviceNs => {
+        buf.extend_from_slice(br"\\.\");
+        buf.extend_from_slice(&prefix[4..]);
+      }
+      PrefixKind::Unc { separator } => {
+        buf.extend_from_slice(br"\\");
+        buf.extend_from_slice(&prefix[2..separator]);
+        buf.push(b'\\');
+        buf.extend_from_slice(&prefix[separator + 1..]);
+      }
+      PrefixKind::Verbatim
+      | PrefixKind::VerbatimUnc
+      | PrefixKind::VerbatimDisk
+      | PrefixKind::Disk => {
+        buf.extend_from_slice(prefix);
+      }
+    }
+  }
+}
+
+/// Parses a Windows prefix the way `std::path::Path::components` does.
+fn parse_windows_prefix(path: &[u8]) -> Option<WindowsPrefix> {
+  if !has_marker(path, 0, br"\\") {
+    return match path {
+      [drive, b':', ..] if drive.is_ascii_alphabetic() => {
+        Some(WindowsPrefix { kind: PrefixKind::Disk, len: 2 })
+      }
+      _ => None,
+    };
+  }
+
+  // A verbatim marker spelled with `/` is not verbatim.
+  if has_marker(path, 2, br"?\") && !path[..4].contains(&b'/') {
+    if has_marker(path, 4, br"UNC\") {
+      let server_end = component_end(path, 8, true);
+      let share_end = component_end(path, (server_end + 1).min(path.len()), true);
+      let len = if share_end > server_end + 1 { share_end } else { server_end };
+      return Some(WindowsPrefix { kind: PrefixKind::VerbatimUnc, len });
+    }
+    if let [_, _, _, _, drive, b':', rest @ ..] = path
+      && drive.is_ascii_alphabetic()
+      && rest.first().is_none_or(|&byte| byte == b'\\')
+    {
+      return Some(WindowsPrefix { kind: PrefixKind::VerbatimDisk, len: 6 });
+    }
+    return Some(WindowsPrefix { kind: PrefixKind::Verbatim, len: component_end(path, 4, true) });
+  }
+
+  if has_marker(path, 2, br".\") {
+    let len = component_end(path, 4, false);
+    return Some(WindowsPrefix { kind: PrefixKind::DeviceNs, len });
+  }
+
+  let server_end = component_end(path, 2, false);
+  if server_end == 2 || server_end == path.len() {
+    return None;
+  }
+  let share_end = component_end(path, server_end + 1, false);
+  if share_end == server_end + 1 {
+    return None;
+  }
+  Some(WindowsPrefix { kind: PrefixKind::Unc { separator: server_end }, len: share_end })
+}
+
+/// Whether `marker` appears at `offset`, reading `/` in `path` as `\`.
+fn has_marker(path: &[u8], offset: usize, marker: &[u8]) -> bool {
+  path.get(offset..offset + marker.len()).is_some_and(|bytes| {
+    bytes.iter().zip(marker).all(|(&byte, &expected)| {
+      let byte = if byte == b'/' { b'\\' } else { byte };
+      byte == expected
+    })
+  })
+}
+
+/// The end of the component that starts at `start`.
+fn component_end(path: &[u8], start: usize, verbatim: bool) -> usize {
+  path[start..]
+    .iter()
+    .position(|&byte| byte == b'\\' || (byte == b'/' && !verbatim))
+    .map_or(path.len(), |position| start + position)
+}
@@ -0,0 +1,188 @@
+//! Compile-time normalization of Unix-syntax string literals.
+//!
+//! [`const_normalize!`](crate::const_normalize) expands to const items that
+//! call the `const fn`s below. The first pass writes into an array with room
+//! for the longest possible result, and the second copies the written prefix
+//! into an array of the exact length so the final `&'static str` carries no
+//! padding.
+
+/// Lexically normalizes a Unix-syntax `path` into the front of a `[u8; N]`
+/// array and returns it with the written length.
+///
+/// This is the algorithm of [`bytes::normalize`](crate::bytes::normalize) for
+/// [`Syntax::Unix`](crate::bytes::Syntax::Unix), restricted to what a
+/// `const fn` can express. `N` must be at least `path.len() + 2`.
+pub const fn normalize_unix<const N: usize>(path: &[u8]) -> ([u8; N], usize) {
+  let mut buf = [0; N];
+  let mut len = 0;
+  let has_root = !path.is_empty() && path[0] == b'/';
+  if has_root {
+    buf[0] = b'/';
+    len = 1;
+  }
+  let root_end = len;
+
+  let mut depth = 0;
+  let mut need_sep = false;
+  let mut start = 0;
+  while start <= path.len() {
+    let mut end = start;
+    while end < path.len() && path[end] != b'/' {
+      if path[end] == 0 {
+        panic!("path contains a NUL byte");
+      }
+      end += 1;
+    }
+
+    let component_len = end - start;
+    let is_cur_dir = component_len == 0 || (component_len == 1 && path[start] == b'.');
+    let is_parent_dir = component_len == 2 && path[start] == b'.' && path[start + 1] == b'.';
+    if is_parent_dir {
+      if depth > 0 {
+        // Roll back the last normal component.
+        while len > root_end && buf[len - 1] != b'/' {
+          len -= 1;
+        }
+        if len > root_end {
+          len -= 1;
+        }
+        depth -= 1;
+        need_sep = len > root_end;
+      } else if !has_root {
+        if need_sep {
+          buf[len] = b'/';
+          len += 1;
+        }
+        buf[len] = b'.';
+        buf[len + 1] = b'.';
+        len += 2;
+        need_sep = true;
+      }
+    } else if !is_cur_dir {
+      if need_sep {
+        buf[len] = b'/';
+        len += 1;
+      }
+      let mut index = start;
+      while index < end {
+        buf[len] = path[index];
+        len += 1;
+        index += 1;
+      }
+      depth += 1;
+      need_sep = true;
+    }
+    start = end + 1;
+  }
+
+  let preserve_trailing = !path.is_empty() && path[path.len() - 1] == b'/';
+  if len == 0 {
+    buf[0] = b'.';
+    len = 1;
+    if preserve_trailing {
+      buf[1] = b'/';
+      len = 2;
+    }
+  } else if preserve_trailing && buf[len - 1] != b'/' {
+    buf[len] = b'/';
+    len += 1;
+  }
+  (buf, len)
+}
+
+/// Copies the first `M` bytes of `buf`.
+pub const fn truncate<const N: usize, const M: usize>(buf: &[u8; N]) -> [u8; M] {
+  let mut out = [0; M];
+  let mut index = 0;
+  while index < M {
+    out[index] = buf[index];
+    index += 1;
+  }
+  out
+}
+
+/// Lexically normalizes a Unix-syntax string at compile time and evaluates to
+/// a `&'static str`.
+///
+/// The argument must be a constant `&str` expression, such as a literal or a
+/// `const` item. The result equals the bytes of
+/// [`bytes::normalize`](crate::bytes::normalize) with
+/// [`Syntax::Unix`](crate::bytes::Syntax::Unix), and therefore the runtime
+/// [`SugarPath::normalize`](crate::SugarPath::normalize) result on Unix and
+/// WASI hosts. `\` is an ordinary character, as it is in POSIX paths.
+///
+/// The macro can initialize `const` and `static` items and is usable without
+/// the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// const ROLLDOWN_SRC: &str = sugar_path::const_normalize!("./crates/rolldown/src/../src");
+/// assert_eq!(ROLLDOWN_SRC, "crates/rolldown/src");
+///
+/// const DIST: &str = "/workspace/./dist//";
+/// static ASSETS: &str = sugar_path::const_normalize!(DIST);
+/// assert_eq!(ASSETS, "/workspace/dist/");
+/// ```
+///
+/// Inputs that are not a valid path fail to compile. A NUL byte cannot appear
+/// in a Unix path:
+///
+/// ```compile_fail
+/// const BAD: &str = sugar_path::const_normalize!("src/\0/lib.rs");
+/// ```
+///
+/// A runtime value is not a constant:
+///
+/// ```compile_fail
+/// let input = String::from("src/../lib.rs");
+/// let _ = sugar_path::const_normalize!(input.as_str());
+/// ```
+#[macro_export]
+macro_rules! const_normalize {
+  ($path:expr $(,)?) => {{
+    const __SUGAR_PATH_INPUT: &str = $path;
+    const __SUGAR_PATH_BUF: ([u8; __SUGAR_PATH_INPUT.len() + 2], usize) =
+      $crate::__private::normalize_unix(__SUGAR_PATH_INPUT.as_bytes());
+    const __SUGAR_PATH_BYTES: [u8; __SUGAR_PATH_BUF.1] =
+      $crate::__private::truncate(&__SUGAR_PATH_BUF.0);
+    const __SUGAR_PATH_OUTPUT: &str = match ::core::str::from_utf8(&__SUGAR_PATH_BYTES) {
+      Ok(normalized) => normalized,
+      Err(_) => panic!("normalizing ASCII path syntax preserves UTF-8"),
+    };
+    __SUGAR_PATH_OUTPUT
+  }};
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::normalize_unix;
+  use crate::bytes::{Syntax, normalize};
+
+  #[test]
+  fn const_normalizer_matches_unix_syntax_normalize_for_short_arbitrary_bytes() {
+    const ALPHABET: [u8; 4] = [b'a', b'/', b'.', 0xFF];
+    let mut inputs = vec![Vec::new()];
+    for _ in 0..7 {
+      let longer: Vec<_> = inputs
+        .iter()
+        .filter(|input| input.len() == inputs.last().unwrap().len())
+        .flat_map(|input| {
+          ALPHABET.iter().map(move |&byte| {
+            let mut input = input.clone();
+            input.push(byte);
+            input
+          })
+        })
+        .collect();
+      inputs.extend(longer);
+    }
+
+    for input in inputs {
+      let (buf, len) = normalize_unix::<9>(&input);
+      assert_eq!(&buf[..len], &*normalize(&input, Syntax::Unix), "{input:?}");
+    }
+  }
+}
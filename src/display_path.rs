@@ -0,0 +1,271 @@
+use std::{
+  borrow::Cow,
+  ffi::OsStr,
+  io,
+  path::{Component, Path, PathBuf},
+};
+
+use crate::{
+  SugarPath,
+  impl_sugar_path::{
+    contract_home_prefix, path_buf_into_slash, path_buf_into_slash_lossy, relative_display,
+    shortest_display, try_path_buf_into_slash,
+  },
+  utils::try_get_current_dir,
+};
+
+const ELLIPSIS: &str = "…";
+
+/// Which spelling [`DisplayPath`] chooses for a path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DisplayPolicy {
+  /// Relative to the cwd when the path is inside it, `~`-contracted when it is
+  /// inside the home directory, and absolute otherwise.
+  #[default]
+  Preferred,
+  /// The shortest of the relative, `~`-contracted, and absolute spellings, as
+  /// chosen by [`crate::SugarPath::contract_home_with`].
+  Shortest,
+  /// Always the absolute path.
+  Absolute,
+}
+
+/// Chooses a human-friendly spelling of paths for diagnostics.
+///
+/// A chooser resolves paths against a fixed absolute cwd and an optional home
+/// directory, picks a spelling with its [`DisplayPolicy`], and optionally
+/// shortens the result to a width budget with a middle ellipsis. The spelling
+/// is returned as a native path with [`DisplayPath::display`], or converted to
+/// `/` separators with [`DisplayPath::to_slash`], [`DisplayPath::try_to_slash`],
+/// or [`DisplayPath::to_slash_lossy`], which follow the Unicode policies of
+/// the [`crate::SugarPath`] methods with the same names.
+///
+/// Everything is lexical: the chooser does not access the filesystem or
+/// resolve symbolic links, and it reads process state only in
+/// [`DisplayPath::from_env`].
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::DisplayPath;
+///
+/// #[cfg(target_family = "unix")]
+/// {
+///   let display = DisplayPath::new("/home/alice/project").home("/home/alice");
+///   assert_eq!(display.to_slash("/home/alice/project/src/main.rs"), "src/main.rs");
+///   assert_eq!(display.to_slash("/home/alice/notes.md"), "~/notes.md");
+///   assert_eq!(display.to_slash("/etc/hosts"), "/etc/hosts");
+///
+///   let narrow = display.max_width(32);
+///   assert_eq!(
+///     narrow.to_slash("src/runtime/module_loader/module_task.rs"),
+///     "…/module_loader/module_task.rs",
+///   );
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DisplayPath {
+  cwd: PathBuf,
+  home: Option<PathBuf>,
+  policy: DisplayPolicy,
+  max_width: Option<usize>,
+}
+
+impl DisplayPath {
+  /// Creates a chooser that resolves paths against `cwd`, with no home
+  /// directory, the [`DisplayPolicy::Preferred`] policy, and no width budget.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `cwd` is not absolute.
+  pub fn new(cwd: impl Into<PathBuf>) -> Self {
+    let cwd = cwd.into();
+    assert!(cwd.is_absolute(), "the display cwd must be absolute: {}", cwd.display());
+    Self { cwd, home: None, policy: DisplayPolicy::default(), max_width: None }
+  }
+
+  /// Creates a chooser for the process cwd and, when it is known, the user's
+  /// home directory.
+  ///
+  /// # Errors
+  ///
+  /// Returns the error from reading the process cwd.
+  pub fn from_env() -> io::Result<Self> {
+    let display = Self::new(try_get_current_dir()?.into_owned());
+    Ok(match std::env::home_dir() {
+      Some(home) => display.home(home),
+      None => display,
+    })
+  }
+
+  /// Sets the directory that is contracted to `~`. An empty or relative
+  /// `home` contracts nothing.
+  pub fn home(mut self, home: impl Into<PathBuf>) -> Self {
+    self.home = Some(home.into());
+    self
+  }
+
+  /// Sets how the spelling is chosen.
+  pub fn policy(mut self, policy: DisplayPolicy) -> Self {
+    self.policy = policy;
+    self
+  }
+
+  /// Sets the width budget, counted in Unicode scalar values.
+  ///
+  /// A longer spelling keeps as many trailing components as fit after `…`,
+  /// and keeps its root, `~`, or first component in front of the `…` when
+  /// that does not cost a trailing component. The final component is never
+  /// cut, so a file name wider than the budget is returned as `…/name`. Width
+  /// is not measured in terminal columns, and invalid encoding counts one
+  /// replacement character per invalid sequence.
+  pub fn max_width(mut self, max_width: usize) -> Self {
+    self.max_width = Some(max_width);
+    self
+  }
+
+  /// Returns the chosen spelling of `path` with native separators.
+  ///
+  /// A relative `path` is resolved against the cwd. The result borrows from
+  /// `path` when the chosen spelling is a suffix of it and needs no ellipsis.
+  pub fn display<'a, P>(&self, path: &'a P) -> Cow<'a, Path>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    let spelled = self.spell(path.as_ref());
+    match self.max_width {
+      Some(max_width) => elide(spelled, max_width),
+      None => spelled,
+    }
+  }
+
+  /// Returns [`DisplayPath::display`] with `/` separators.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the chosen spelling is not valid Unicode. Use
+  /// [`DisplayPath::try_to_slash`] or [`DisplayPath::to_slash_lossy`] for
+  /// paths that may not be.
+  pub fn to_slash<'a, P>(&self, path: &'a P) -> Cow<'a, str>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    match self.display(path) {
+      Cow::Borrowed(path) => path.to_slash(),
+      Cow::Owned(path) => Cow::Owned(path_buf_into_slash(path)),
+    }
+  }
+
+  /// Returns [`DisplayPath::display`] with `/` separators, or `None` if the
+  /// chosen spelling is not valid Unicode.
+  pub fn try_to_slash<'a, P>(&self, path: &'a P) -> Option<Cow<'a, str>>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    match self.display(path) {
+      Cow::Borrowed(path) => path.try_to_slash(),
+      Cow::Owned(path) => try_path_buf_into_slash(path).ok().map(Cow::Owned),
+    }
+  }
+
+  /// Returns [`DisplayPath::display`] with `/` separators, replacing invalid
+  /// encoding with the Unicode replacement character.
+  pub fn to_slash_lossy<'a, P>(&self, path: &'a P) -> Cow<'a, str>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    match self.display(path) {
+      Cow::Borrowed(path) => path.to_slash_lossy(),
+      Cow::Owned(path) => Cow::Owned(path_buf_into_slash_lossy(path)),
+    }
+  }
+
+  fn spell<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+    let cwd = self.cwd.as_path();
+    match self.policy {
+      DisplayPolicy::Preferred => {
+        let relative = relative_display(path, cwd);
+        if is_descendant(&relative) {
+          return relative;
+        }
+        let absolute = path.absolutize_with(cwd);
+        match self.home.as_deref().and_then(|home| contract_home_prefix(&absolute, home)) {
+          Some(contracted) => Cow::Owned(contracted),
+          None => absolute,
+        }
+      }
+      DisplayPolicy::Shortest => shortest_display(path, self.home.as_deref(), cwd),
+      DisplayPolicy::Absolute => path.absolutize_with(cwd),
+    }
+  }
+}
+
+/// Returns `true` for a relative path that does not leave its base.
+fn is_descendant(relative: &Path) -> bool {
+  relative.is_relative() && !matches!(relative.components().next(), Some(Component::ParentDir))
+}
+
+fn width(text: &OsStr) -> usize {
+  text.to_string_lossy().chars().count()
+}
+
+/// Shortens `path` to `max_width` by replacing middle components with `…`.
+fn elide(path: Cow<'_, Path>, max_width: usize) -> Cow<'_, Path> {
+  match elided(&path, max_width) {
+    Some(elided) => Cow::Owned(elided),
+    None => path,
+  }
+}
+
+fn elided(path: &Path, max_width: usize) -> Option<PathBuf> {
+  let full_width = width(path.as_os_str());
+  if full_width <= max_width {
+    return None;
+  }
+
+  let mut components = path.components().peekable();
+  let mut head = PathBuf::new();
+  while let Some(component @ (Component::Prefix(_) | Component::RootDir)) = components.peek() {
+    head.push(component);
+    components.next();
+  }
+  // A relative head is joined to the ellipsis with a separator; a root
+  // already ends with one, and a bare drive prefix such as `C:` takes none.
+  let mut head_separator = 0;
+  if head.as_os_str().is_empty() {
+    head.push(components.next()?);
+    head_separator = 1;
+  }
+  let rest: Vec<_> = components.collect();
+  if rest.is_empty() {
+    return None;
+  }
+
+  // Counts the trailing components that fit after `…`, up to `available`.
+  let fitting = |prefix_width: usize, available: usize| {
+    let mut used = prefix_width + ELLIPSIS.chars().count();
+    let mut count = 0;
+    for component in rest.iter().rev().take(available) {
+      used += 1 + width(component.as_os_str());
+      if used > max_width {
+        break;
+      }
+      count += 1;
+    }
+    count
+  };
+  // With the head kept, at least one component must still be elided.
+  let with_head = fitting(width(head.as_os_str()) + head_separator, rest.len() - 1);
+  let without_head = fitting(0, rest.len()).max(1);
+
+  let (mut elided, kept) = if with_head >= without_head {
+    head.push(ELLIPSIS);
+    (head, with_head)
+  } else {
+    (PathBuf::from(ELLIPSIS), without_head)
+  };
+  for component in &rest[rest.len() - kept..] {
+    elided.push(component);
+  }
+  (width(elided.as_os_str()) < full_width).then_some(elided)
+}
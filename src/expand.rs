@@ -0,0 +1,241 @@
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  error::Error,
+  ffi::{OsStr, OsString},
+  fmt,
+  path::{Path, PathBuf},
+};
+
+use crate::{SugarPath, impl_sugar_path::normalize_owned_path_buf};
+
+/// The home directory and variables used by [`SugarPath::expand_with`].
+///
+/// An explicit environment keeps expansion independent of process state. Build
+/// one with [`ExpandEnv::home`] and [`ExpandEnv::var`], or collect variables
+/// from name-value pairs. Values are [`OsString`]s, so non-UTF-8 values are
+/// preserved. On Windows, names are matched with ASCII case ignored, like the
+/// process environment.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::ExpandEnv;
+///
+/// let env: ExpandEnv = [("XDG_CONFIG_HOME", "/home/alice/.config")].into_iter().collect();
+/// let env = env.home("/home/alice").var("PROJECT", "sugar_path");
+/// # let _ = env;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ExpandEnv {
+  home: Option<PathBuf>,
+  vars: HashMap<String, OsString>,
+}
+
+impl ExpandEnv {
+  /// Creates an environment with no home directory and no variables.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the directory that `~` expands to.
+  pub fn home(mut self, home: impl Into<PathBuf>) -> Self {
+    self.home = Some(home.into());
+    self
+  }
+
+  /// Sets a variable, replacing an earlier value with the same name.
+  pub fn var(mut self, name: impl Into<String>, value: impl Into<OsString>) -> Self {
+    self.insert(name.into(), value.into());
+    self
+  }
+
+  fn insert(&mut self, name: String, value: OsString) {
+    self.vars.insert(fold_name(name), value);
+  }
+
+  fn lookup(&self, name: &str) -> Option<&OsStr> {
+    #[cfg(target_family = "windows")]
+    let name = &name.to_ascii_uppercase();
+    self.vars.get(name).map(OsString::as_os_str)
+  }
+}
+
+fn fold_name(name: String) -> String {
+  #[cfg(target_family = "windows")]
+  {
+    name.to_ascii_uppercase()
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    name
+  }
+}
+
+impl<K, V> FromIterator<(K, V)> for ExpandEnv
+where
+  K: Into<String>,
+  V: Into<OsString>,
+{
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut env = Self::new();
+    env.extend(iter);
+    env
+  }
+}
+
+impl<K, V> Extend<(K, V)> for ExpandEnv
+where
+  K: Into<String>,
+  V: Into<OsString>,
+{
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    for (name, value) in iter {
+      self.insert(name.into(), value.into());
+    }
+  }
+}
+
+/// The error returned by [`SugarPath::expand_with`] and [`SugarPath::expand`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExpandError {
+  /// A referenced variable is not set.
+  UnknownVariable {
+    /// The variable name as written, without `$`, braces, or `%`.
+    name: String,
+  },
+  /// The path starts with `~`, but no home directory is known.
+  MissingHome,
+  /// A `${` has no closing `}`.
+  UnclosedBrace,
+}
+
+impl fmt::Display for ExpandError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnknownVariable { name } => write!(f, "environment variable `{name}` is not set"),
+      Self::MissingHome => f.write_str("the home directory is unknown"),
+      Self::UnclosedBrace => f.write_str("`${` has no closing `}`"),
+    }
+  }
+}
+
+impl Error for ExpandError {}
+
+pub(crate) fn expand_with<'a>(
+  path: &'a Path,
+  env: &ExpandEnv,
+) -> Result<Cow<'a, Path>, ExpandError> {
+  expand_path(path, || env.home.as_deref(), |name| env.lookup(name))
+}
+
+pub(crate) fn expand_ambient(path: &Path) -> Result<Cow<'_, Path>, ExpandError> {
+  expand_path(path, std::env::home_dir, |name: &str| std::env::var_os(name))
+}
+
+fn expand_path<'a, H, V>(
+  path: &'a Path,
+  home: impl FnOnce() -> Option<H>,
+  var: impl Fn(&str) -> Option<V>,
+) -> Result<Cow<'a, Path>, ExpandError>
+where
+  H: AsRef<Path>,
+  V: AsRef<OsStr>,
+{
+  let bytes = path.as_os_str().as_encoded_bytes();
+  let mut expanded = OsString::new();
+  // Start of the input text not yet copied to `expanded`.
+  let mut copied = 0;
+  let mut index = 0;
+
+  if bytes.first() == Some(&b'~') && bytes.get(1).is_none_or(|&byte| is_separator(byte)) {
+    let home = home().ok_or(ExpandError::MissingHome)?;
+    expanded.push(home.as_ref());
+    copied = 1;
+    index = 1;
+  }
+
+  while index < bytes.len() {
+    let Some((name, end)) = variable_at(bytes, index)? else {
+      index += 1;
+      continue;
+    };
+    let value = var(name).ok_or_else(|| ExpandError::UnknownVariable { name: name.to_owned() })?;
+    expanded.push(slice(bytes, copied, index));
+    expanded.push(value);
+    copied = end;
+    index = end;
+  }
+
+  if copied == 0 {
+    return Ok(path.normalize());
+  }
+  expanded.push(slice(bytes, copied, bytes.len()));
+  Ok(Cow::Owned(normalize_owned_path_buf(PathBuf::from(expanded))))
+}
+
+/// Parses a variable reference starting at `bytes[start]`, returning its name
+/// and the index just past it.
+fn variable_at(bytes: &[u8], start: usize) -> Result<Option<(&str, usize)>, ExpandError> {
+  match bytes[start] {
+    b'$' if bytes.get(start + 1) == Some(&b'{') => {
+      let name_start = start + 2;
+      let Some(len) = memchr::memchr(b'}', &bytes[name_start..]) else {
+        return Err(ExpandError::UnclosedBrace);
+      };
+      let name = &bytes[name_start..name_start + len];
+      Ok(as_variable_name(name).map(|name| (name, name_start + len + 1)))
+    }
+    b'$' => {
+      let name_start = start + 1;
+      let len = bytes[name_start..]
+        .iter()
+        .position(|&byte| !(byte.is_ascii_alphanumeric() || byte == b'_'))
+        .unwrap_or(bytes.len() - name_start);
+      let name = &bytes[name_start..name_start + len];
+      Ok(as_variable_name(name).map(|name| (name, name_start + len)))
+    }
+    #[cfg(target_family = "windows")]
+    b'%' => {
+      let name_start = start + 1;
+      let Some(len) = memchr::memchr(b'%', &bytes[name_start..]) else {
+        return Ok(None);
+      };
+      // Like `cmd.exe`, a `%` pair that cannot name a variable stays literal.
+      let name = std::str::from_utf8(&bytes[name_start..name_start + len])
+        .ok()
+        .filter(|name| !name.is_empty() && !name.bytes().any(is_separator));
+      Ok(name.map(|name| (name, name_start + len + 1)))
+    }
+    _ => Ok(None),
+  }
+}
+
+/// Returns a `$` variable name: an ASCII letter or `_`, then ASCII letters,
+/// digits, or `_`.
+fn as_variable_name(name: &[u8]) -> Option<&str> {
+  let (first, rest) = name.split_first()?;
+  let valid = (first.is_ascii_alphabetic() || *first == b'_')
+    && rest.iter().all(|byte| byte.is_ascii_alphanumeric() || *byte == b'_');
+  // SAFETY: `name` is non-empty ASCII when `valid` is true.
+  valid.then(|| unsafe { std::str::from_utf8_unchecked(name) })
+}
+
+fn slice(bytes: &[u8], start: usize, end: usize) -> &OsStr {
+  // SAFETY: callers split only before or after the ASCII bytes `~`, `$`, `}`,
+  // `%`, or an ASCII variable-name byte, which are encoded-character
+  // boundaries.
+  unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[start..end]) }
+}
+
+fn is_separator(byte: u8) -> bool {
+  #[cfg(target_family = "windows")]
+  {
+    matches!(byte, b'\\' | b'/')
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    byte == b'/'
+  }
+}
@@ -0,0 +1,105 @@
+use std::{
+  ffi::{OsStr, OsString},
+  path::{Path, PathBuf},
+};
+
+use memchr::{memchr, memrchr};
+
+/// A file name and its byte range within the encoded path.
+struct FileName<'a> {
+  name: &'a [u8],
+  start: usize,
+}
+
+impl<'a> FileName<'a> {
+  fn of(path: &'a Path) -> Option<Self> {
+    let name = path.file_name()?.as_encoded_bytes();
+    // `Path::file_name` borrows from the path, so the offset is in bounds.
+    let start = name.as_ptr() as usize - path.as_os_str().as_encoded_bytes().as_ptr() as usize;
+    Some(Self { name, start })
+  }
+
+  /// Splits the file name at the dot with index `dot`.
+  fn split_at(&self, dot: usize) -> (&'a OsStr, &'a OsStr) {
+    // SAFETY: `dot` indexes an ASCII `.`, which is an encoded-character
+    // boundary.
+    unsafe {
+      (
+        OsStr::from_encoded_bytes_unchecked(&self.name[..dot]),
+        OsStr::from_encoded_bytes_unchecked(&self.name[dot + 1..]),
+      )
+    }
+  }
+}
+
+// A dot at the start of a file name marks a dotfile such as `.eslintrc`, not
+// an extension, the same rule Node's `path.extname` uses.
+
+fn first_extension_dot(name: &[u8]) -> Option<usize> {
+  memchr(b'.', name.get(1..)?).map(|index| index + 1)
+}
+
+fn last_extension_dot(name: &[u8]) -> Option<usize> {
+  memrchr(b'.', name).filter(|&index| index > 0)
+}
+
+fn extension_eq(a: &[u8], b: &[u8]) -> bool {
+  #[cfg(target_family = "windows")]
+  {
+    a.eq_ignore_ascii_case(b)
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    a == b
+  }
+}
+
+pub(crate) fn full_extension(path: &Path) -> Option<&OsStr> {
+  let file_name = FileName::of(path)?;
+  first_extension_dot(file_name.name).map(|dot| file_name.split_at(dot).1)
+}
+
+pub(crate) fn split_extension_with<'a, S: AsRef<OsStr>>(
+  path: &'a Path,
+  known: &[S],
+) -> Option<(&'a OsStr, &'a OsStr)> {
+  let file_name = FileName::of(path)?;
+  let name = file_name.name;
+  let known_dot = known
+    .iter()
+    .map(|extension| extension.as_ref().as_encoded_bytes())
+    .filter(|extension| !extension.is_empty() && extension.len() + 1 < name.len())
+    .filter_map(|extension| {
+      let dot = name.len() - extension.len() - 1;
+      (name[dot] == b'.' && extension_eq(&name[dot + 1..], extension)).then_some(dot)
+    })
+    .min();
+  known_dot.or_else(|| last_extension_dot(name)).map(|dot| file_name.split_at(dot))
+}
+
+pub(crate) fn with_full_extension(path: &Path, extension: &OsStr) -> PathBuf {
+  let extension = extension.as_encoded_bytes();
+  assert!(
+    !extension.iter().any(|&byte| byte.is_ascii() && std::path::is_separator(char::from(byte))),
+    "the extension contains a path separator",
+  );
+  let Some(file_name) = FileName::of(path) else {
+    return path.to_path_buf();
+  };
+
+  let bytes = path.as_os_str().as_encoded_bytes();
+  let stem_end =
+    file_name.start + first_extension_dot(file_name.name).unwrap_or(file_name.name.len());
+  let name_end = file_name.start + file_name.name.len();
+  let mut replaced = Vec::with_capacity(bytes.len() + extension.len() + 1);
+  replaced.extend_from_slice(&bytes[..stem_end]);
+  if !extension.is_empty() {
+    replaced.push(b'.');
+    replaced.extend_from_slice(extension);
+  }
+  replaced.extend_from_slice(&bytes[name_end..]);
+  // SAFETY: `replaced` joins the receiver's bytes, split at the start of an
+  // ASCII `.` or at file-name boundaries, with an ASCII `.` and the encoded
+  // bytes of an `OsStr`.
+  PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(replaced) })
+}
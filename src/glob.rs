@@ -0,0 +1,525 @@
+//! Glob patterns matched against lexically normalized paths.
+//!
+//! A [`Pattern`] is compiled once and then matched against many [`Path`]s
+//! component by component, on native encoded bytes, without converting the
+//! path to a [`String`]. Both sides follow the crate's lexical rules: the
+//! path is normalized the way [`crate::SugarPath::relative`] normalizes its
+//! inputs, and the pattern's `.` segments, repeated separators, and `..`
+//! after a literal segment are resolved the same way, so `a/./b` and
+//! `a/x/../b` both match the pattern `a/b`.
+//!
+//! # Syntax
+//!
+//! Patterns are separated by `/` on every platform.
+//!
+//! | Syntax | Matches |
+//! | --- | --- |
+//! | `?` | one character of a component |
+//! | `*` | any run of characters within one component, including a leading `.` |
+//! | `**` | as a whole segment, zero or more components |
+//! | `[abc]`, `[a-z]` | one character in the class |
+//! | `[!a-z]`, `[^a-z]` | one character not in the class |
+//! | `{a,b/c}` | any of the comma-separated alternatives, which may nest and contain `/` |
+//! | `\c` | the character `c` literally |
+//!
+//! A pattern that starts with `/` matches only rooted paths. On Windows, a
+//! pattern that starts with a drive such as `C:/` matches only paths on that
+//! drive. A path's `..` components are matched only by a literal `..`
+//! segment or by `**`.
+//!
+//! # Examples
+//!
+//! ```
+//! use sugar_path::glob::Pattern;
+//!
+//! let pattern = Pattern::new("src/**/*.{ts,tsx}").unwrap();
+//! assert!(pattern.matches("src/components/./button.tsx"));
+//! assert!(pattern.matches("src/index.ts"));
+//! assert!(!pattern.matches("test/index.ts"));
+//! ```
+
+use std::{
+  collections::HashSet,
+  error::Error,
+  fmt,
+  path::{Component, Path},
+};
+
+use smallvec::SmallVec;
+
+use crate::impl_sugar_path::normalize_for_resolution;
+
+/// The most brace alternatives one pattern may expand to. Each alternative
+/// is compiled separately, so chained braces multiply the work.
+const MAX_ALTERNATIVES: usize = 1024;
+
+/// A compiled glob pattern.
+///
+/// Matching ignores ASCII case by default on Windows, the same rule used by
+/// [`crate::SugarPath::relative`], and is case-sensitive elsewhere. Use
+/// [`Pattern::case_sensitive`] to choose explicitly.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+  source: String,
+  /// One segment list per brace alternative.
+  alternatives: Vec<Box<[Segment]>>,
+  case_sensitive: bool,
+}
+
+/// The error returned by [`Pattern::new`] for malformed patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatternError {
+  /// A `[` has no closing `]`.
+  UnclosedClass {
+    /// The byte index of the `[`.
+    position: usize,
+  },
+  /// A `{` has no closing `}`.
+  UnclosedBrace {
+    /// The byte index of the `{`.
+    position: usize,
+  },
+  /// A class range such as `[z-a]` ends before it starts.
+  InvalidRange {
+    /// The byte index of the range start.
+    position: usize,
+  },
+  /// The pattern ends with an unescaped `\`.
+  DanglingEscape,
+  /// The braces expand to more alternatives than a pattern may compile.
+  TooManyAlternatives {
+    /// The most alternatives a pattern may expand to.
+    limit: usize,
+  },
+}
+
+impl fmt::Display for PatternError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnclosedClass { position } => write!(f, "`[` at byte {position} has no closing `]`"),
+      Self::UnclosedBrace { position } => write!(f, "`{{` at byte {position} has no closing `}}`"),
+      Self::InvalidRange { position } => {
+        write!(f, "the character range at byte {position} ends before it starts")
+      }
+      Self::DanglingEscape => f.write_str("the pattern ends with an unescaped `\\`"),
+      Self::TooManyAlternatives { limit } => {
+        write!(f, "the braces expand to more than {limit} alternatives")
+      }
+    }
+  }
+}
+
+impl Error for PatternError {}
+
+impl Pattern {
+  /// Compiles `pattern`.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`PatternError`] for an unclosed `[` or `{`, a reversed class
+  /// range, or a trailing `\`. Braces that expand to more than 1024
+  /// alternatives, such as eleven chained `{a,b}` groups, are rejected with
+  /// [`PatternError::TooManyAlternatives`] before any are compiled.
+  pub fn new(pattern: &str) -> Result<Self, PatternError> {
+    let mut parser = Parser { pattern, position: 0 };
+    let nodes = parser.sequence(false)?;
+    if alternative_count(&nodes) > MAX_ALTERNATIVES {
+      return Err(PatternError::TooManyAlternatives { limit: MAX_ALTERNATIVES });
+    }
+    let mut alternatives: Vec<Box<[Segment]>> = Vec::new();
+    let mut seen = HashSet::new();
+    for flat in expand(&nodes) {
+      let segments = segments(&flat);
+      if seen.insert(segments.clone()) {
+        alternatives.push(segments);
+      }
+    }
+    Ok(Self {
+      source: pattern.to_owned(),
+      alternatives,
+      case_sensitive: cfg!(not(target_family = "windows")),
+    })
+  }
+
+  /// Sets whether characters must match in case. When `false`, ASCII letters
+  /// match either case; other characters still match exactly.
+  pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+    self.case_sensitive = case_sensitive;
+    self
+  }
+
+  /// Returns the pattern text this was compiled from.
+  pub fn as_str(&self) -> &str {
+    &self.source
+  }
+
+  /// Returns `true` if the lexically normalized `path` matches the pattern.
+  ///
+  /// The path is not accessed on disk, and non-UTF-8 components are matched
+  /// on their encoded bytes: each invalid byte is one character that only
+  /// `?`, `*`, `**`, or a negated class can match.
+  pub fn matches(&self, path: impl AsRef<Path>) -> bool {
+    let normalized = normalize_for_resolution(path.as_ref());
+    let items: SmallVec<[Item<'_>; 16]> = normalized
+      .components()
+      .filter_map(|component| match component {
+        Component::Prefix(prefix) => Some(Item::Name(prefix.as_os_str().as_encoded_bytes())),
+        Component::RootDir => Some(Item::Root),
+        Component::CurDir => None,
+        Component::ParentDir => Some(Item::Parent),
+        Component::Normal(name) => Some(Item::Name(name.as_encoded_bytes())),
+      })
+      .collect();
+    self.alternatives.iter().any(|segments| match_segments(segments, &items, self.case_sensitive))
+  }
+}
+
+impl fmt::Display for Pattern {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.source)
+  }
+}
+
+/// One component of a normalized path.
+#[derive(Clone, Copy)]
+enum Item<'a> {
+  Root,
+  Parent,
+  Name(&'a [u8]),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Segment {
+  Root,
+  AnyDepth,
+  Literal(Box<[u8]>),
+  Wildcard(Box<[Token]>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Token {
+  Char(char),
+  AnyChar,
+  AnyRun,
+  Class(Class),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Class {
+  negated: bool,
+  ranges: Box<[(char, char)]>,
+}
+
+impl Class {
+  fn matches(&self, ch: Option<char>, case_sensitive: bool) -> bool {
+    let Some(ch) = ch else {
+      return self.negated;
+    };
+    let contains = |ch: char| self.ranges.iter().any(|&(start, end)| (start..=end).contains(&ch));
+    let found = contains(ch)
+      || (!case_sensitive
+        && (contains(ch.to_ascii_lowercase()) || contains(ch.to_ascii_uppercase())));
+    found != self.negated
+  }
+}
+
+/// A parsed pattern before brace expansion.
+enum Node {
+  Separator,
+  Token(Token),
+  Alternatives(Vec<Vec<Node>>),
+}
+
+struct Parser<'a> {
+  pattern: &'a str,
+  position: usize,
+}
+
+impl Parser<'_> {
+  fn peek(&self) -> Option<char> {
+    self.pattern[self.position..].chars().next()
+  }
+
+  fn bump(&mut self) -> Option<char> {
+    let ch = self.peek()?;
+    self.position += ch.len_utf8();
+    Some(ch)
+  }
+
+  /// Parses up to the end of the pattern or, inside braces, up to an
+  /// unescaped `,` or `}`, which is left unconsumed.
+  fn sequence(&mut self, in_braces: bool) -> Result<Vec<Node>, PatternError> {
+    let mut nodes = Vec::new();
+    while let Some(ch) = self.peek() {
+      if in_braces && matches!(ch, ',' | '}') {
+        break;
+      }
+      let start = self.position;
+      self.bump();
+      nodes.push(match ch {
+        '/' => Node::Separator,
+        '?' => Node::Token(Token::AnyChar),
+        '*' => Node::Token(Token::AnyRun),
+        '[' => Node::Token(Token::Class(self.class(start)?)),
+        '{' => Node::Alternatives(self.alternatives(start)?),
+        '\\' => Node::Token(Token::Char(self.bump().ok_or(PatternError::DanglingEscape)?)),
+        _ => Node::Token(Token::Char(ch)),
+      });
+    }
+    Ok(nodes)
+  }
+
+  fn alternatives(&mut self, start: usize) -> Result<Vec<Vec<Node>>, PatternError> {
+    let mut alternatives = Vec::new();
+    loop {
+      alternatives.push(self.sequence(true)?);
+      match self.bump() {
+        Some(',') => {}
+        Some('}') => return Ok(alternatives),
+        _ => return Err(PatternError::UnclosedBrace { position: start }),
+      }
+    }
+  }
+
+  fn class(&mut self, start: usize) -> Result<Class, PatternError> {
+    let unclosed = PatternError::UnclosedClass { position: start };
+    let negated = matches!(self.peek(), Some('!' | '^'));
+    if negated {
+      self.bump();
+    }
+    let mut ranges = Vec::new();
+    let mut first = true;
+    loop {
+      let range_start = self.position;
+      let low = match self.bump().ok_or_else(|| unclosed.clone())? {
+        ']' if !first => break,
+        '\\' => self.bump().ok_or_else(|| unclosed.clone())?,
+        ch => ch,
+      };
+      first = false;
+      let mut high = low;
+      if self.peek() == Some('-') && !self.pattern[self.position + 1..].starts_with(']') {
+        self.bump();
+        high = match self.bump().ok_or_else(|| unclosed.clone())? {
+          '\\' => self.bump().ok_or_else(|| unclosed.clone())?,
+          ch => ch,
+        };
+        if high < low {
+          return Err(PatternError::InvalidRange { position: range_start });
+        }
+      }
+      ranges.push((low, high));
+    }
+    Ok(Class { negated, ranges: ranges.into() })
+  }
+}
+
+/// A pattern element after brace expansion.
+#[derive(Clone)]
+enum Flat {
+  Separator,
+  Token(Token),
+}
+
+/// Counts the flat sequences [`expand`] would produce, saturating instead of
+/// overflowing.
+fn alternative_count(nodes: &[Node]) -> usize {
+  nodes.iter().fold(1, |count: usize, node| match node {
+    Node::Alternatives(alternatives) => count.saturating_mul(
+      alternatives.iter().fold(0, |sum: usize, nodes| sum.saturating_add(alternative_count(nodes))),
+    ),
+    Node::Separator | Node::Token(_) => count,
+  })
+}
+
+/// Expands brace alternatives into every flat sequence they spell.
+fn expand(nodes: &[Node]) -> Vec<Vec<Flat>> {
+  let mut expanded = vec![Vec::new()];
+  for node in nodes {
+    match node {
+      Node::Separator => expanded.iter_mut().for_each(|flat| flat.push(Flat::Separator)),
+      Node::Token(token) => {
+        expanded.iter_mut().for_each(|flat| flat.push(Flat::Token(token.clone())));
+      }
+      Node::Alternatives(alternatives) => {
+        let suffixes: Vec<_> = alternatives.iter().flat_map(|nodes| expand(nodes)).collect();
+        expanded = expanded
+          .iter()
+          .flat_map(|prefix| {
+            suffixes.iter().map(move |suffix| {
+              let mut flat = prefix.clone();
+              flat.extend(suffix.iter().cloned());
+              flat
+            })
+          })
+          .collect();
+      }
+    }
+  }
+  expanded
+}
+
+/// Splits a flat sequence at separators and normalizes the segments the way
+/// paths are normalized.
+fn segments(flat: &[Flat]) -> Box<[Segment]> {
+  let mut parts = flat.split(|element| matches!(element, Flat::Separator));
+  let mut segments = Vec::new();
+  let first = parts.next().unwrap_or_default();
+  if first.is_empty() && !flat.is_empty() {
+    segments.push(Segment::Root);
+  } else {
+    push_segment(&mut segments, tokens(first));
+    #[cfg(target_family = "windows")]
+    if flat.iter().any(|element| matches!(element, Flat::Separator))
+      && let [Segment::Literal(drive)] = segments.as_slice()
+      && let [letter, b':'] = **drive
+      && letter.is_ascii_alphabetic()
+    {
+      segments.push(Segment::Root);
+    }
+  }
+  for part in parts {
+    push_segment(&mut segments, tokens(part));
+  }
+  segments.into()
+}
+
+fn tokens(part: &[Flat]) -> Vec<Token> {
+  part
+    .iter()
+    .map(|element| match element {
+      Flat::Token(token) => token.clone(),
+      Flat::Separator => unreachable!("segments are split at separators"),
+    })
+    .collect()
+}
+
+fn push_segment(segments: &mut Vec<Segment>, tokens: Vec<Token>) {
+  let literal: Option<String> = tokens
+    .iter()
+    .map(|token| match token {
+      Token::Char(ch) => Some(*ch),
+      _ => None,
+    })
+    .collect();
+  let segment = match (literal.as_deref(), tokens.as_slice()) {
+    (Some("" | "."), _) => return,
+    (Some(".."), _) if matches!(segments.last(), Some(Segment::Literal(previous)) if **previous != *b"..") =>
+    {
+      segments.pop();
+      return;
+    }
+    (Some(literal), _) => Segment::Literal(literal.as_bytes().into()),
+    (None, [Token::AnyRun, Token::AnyRun]) if segments.last() == Some(&Segment::AnyDepth) => {
+      return;
+    }
+    (None, [Token::AnyRun, Token::AnyRun]) => Segment::AnyDepth,
+    (None, _) => Segment::Wildcard(tokens.into()),
+  };
+  segments.push(segment);
+}
+
+fn match_segments(segments: &[Segment], items: &[Item<'_>], case_sensitive: bool) -> bool {
+  let (mut segment, mut item) = (0, 0);
+  // The segment after the last `**` and the item it resumes from.
+  let mut backtrack: Option<(usize, usize)> = None;
+  loop {
+    if segment < segments.len() {
+      if segments[segment] == Segment::AnyDepth {
+        backtrack = Some((segment + 1, item));
+        segment += 1;
+        continue;
+      }
+      if item < items.len() && segment_matches(&segments[segment], items[item], case_sensitive) {
+        segment += 1;
+        item += 1;
+        continue;
+      }
+    } else if item == items.len() {
+      return true;
+    }
+    match backtrack {
+      Some((resume, start)) if start < items.len() => {
+        backtrack = Some((resume, start + 1));
+        segment = resume;
+        item = start + 1;
+      }
+      _ => return false,
+    }
+  }
+}
+
+fn segment_matches(segment: &Segment, item: Item<'_>, case_sensitive: bool) -> bool {
+  match (segment, item) {
+    (Segment::Root, Item::Root) => true,
+    (Segment::Literal(literal), Item::Parent) => **literal == *b"..",
+    (Segment::Literal(literal), Item::Name(name)) => {
+      if case_sensitive {
+        **literal == *name
+      } else {
+        literal.eq_ignore_ascii_case(name)
+      }
+    }
+    (Segment::Wildcard(tokens), Item::Name(name)) => match_tokens(tokens, name, case_sensitive),
+    _ => false,
+  }
+}
+
+/// Decodes the character at the start of `bytes`, or `None` for one invalid
+/// byte.
+fn decode(bytes: &[u8]) -> (Option<char>, usize) {
+  let len = match bytes[0] {
+    0x00..=0x7f => return (Some(char::from(bytes[0])), 1),
+    0xc0..=0xdf => 2,
+    0xe0..=0xef => 3,
+    0xf0..=0xf7 => 4,
+    _ => return (None, 1),
+  };
+  match bytes.get(..len).and_then(|bytes| std::str::from_utf8(bytes).ok()) {
+    Some(text) => (text.chars().next(), len),
+    None => (None, 1),
+  }
+}
+
+fn match_tokens(tokens: &[Token], name: &[u8], case_sensitive: bool) -> bool {
+  let (mut token, mut index) = (0, 0);
+  // The token after the last `*` and the name index it resumes from.
+  let mut backtrack: Option<(usize, usize)> = None;
+  loop {
+    if token < tokens.len() {
+      if tokens[token] == Token::AnyRun {
+        backtrack = Some((token + 1, index));
+        token += 1;
+        continue;
+      }
+      if index < name.len() {
+        let (ch, len) = decode(&name[index..]);
+        let matched = match &tokens[token] {
+          Token::Char(expected) => ch.is_some_and(|ch| {
+            ch == *expected || (!case_sensitive && ch.eq_ignore_ascii_case(expected))
+          }),
+          Token::AnyChar => true,
+          Token::Class(class) => class.matches(ch, case_sensitive),
+          Token::AnyRun => unreachable!("handled above"),
+        };
+        if matched {
+          token += 1;
+          index += len;
+          continue;
+        }
+      }
+    } else if index == name.len() {
+      return true;
+    }
+    match backtrack {
+      Some((resume, start)) if start < name.len() => {
+        let next = start + decode(&name[start..]).1;
+        backtrack = Some((resume, next));
+        token = resume;
+        index = next;
+      }
+      _ => return false,
+    }
+  }
+}
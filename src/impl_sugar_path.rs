@@ -3,13 +3,27 @@ use std::{
   ffi::{OsStr, OsString},
   io,
   iter::Peekable,
+  ops::Range,
   path::{Component, Path, PathBuf},
 };
 
 use memchr::{memchr, memrchr};
 use smallvec::SmallVec;
 
-use crate::{SugarPath, utils::try_get_current_dir};
+#[cfg(not(target_family = "windows"))]
+use crate::lexical::unix_bytes_need_normalization;
+#[cfg(target_family = "windows")]
+use crate::lexical::{
+  windows_bytes_need_normalization, windows_standalone_relative_bytes_are_representable,
+};
+use crate::{
+  ExpandEnv, ExpandError, LexicalAncestors, PortabilityIssue, PortabilityTargets, SugarPath,
+  lexical::{
+    LexicalComponent, PathBytes, TrailingSeparator, has_dirty_separator, push_lexical_components,
+  },
+  path_remapper::replace_prefix,
+  utils::try_get_current_dir,
+};
 
 type StrVec<'a> = SmallVec<[&'a str; 8]>;
 type OsStrVec<'a> = SmallVec<[&'a OsStr; 16]>;
@@ -20,14 +34,14 @@ enum OwnedNormalizeOutcome {
   Owned(PathBuf),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum TrailingSeparator {
-  Preserve,
-  Strip,
-}
-
-enum RelativeOutcome<'a> {
+pub(crate) enum RelativeOutcome<'a> {
   BorrowedNative(&'a Path),
+  /// `ups` parent steps followed by `suffix`, a native-separated suffix of
+  /// the receiver. Writing the steps is left to the consumer.
+  Upward {
+    ups: usize,
+    suffix: &'a str,
+  },
   Native(PathBuf),
   Slash(String),
 }
@@ -36,6 +50,13 @@ impl<'a> RelativeOutcome<'a> {
   fn into_path_buf(self) -> PathBuf {
     match self {
       Self::BorrowedNative(path) => path.to_owned(),
+      Self::Upward { ups, suffix } => {
+        let mut bytes = Vec::with_capacity(ups * 3 + suffix.len());
+        write_upward_relative(&mut bytes, ups, suffix);
+        // SAFETY: the bytes are ASCII `..` steps and separators followed by a
+        // `&str`, which is valid in every platform encoding.
+        PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes) })
+      }
       Self::Native(path) => path,
       Self::Slash(path) => {
         #[cfg(target_family = "windows")]
@@ -50,7 +71,7 @@ impl<'a> RelativeOutcome<'a> {
     }
   }
 
-  fn into_cow_path(self) -> Cow<'a, Path> {
+  pub(crate) fn into_cow_path(self) -> Cow<'a, Path> {
     match self {
       Self::BorrowedNative(path) => Cow::Borrowed(path),
       outcome => Cow::Owned(outcome.into_path_buf()),
@@ -58,6 +79,24 @@ impl<'a> RelativeOutcome<'a> {
   }
 }
 
+/// Appends `ups` native `..` steps and then `suffix`, separated by the native
+/// separator.
+pub(crate) fn write_upward_relative(bytes: &mut impl PathBytes, ups: usize, suffix: &str) {
+  let start = bytes.len();
+  for _ in 0..ups {
+    if bytes.len() > start {
+      bytes.push(std::path::MAIN_SEPARATOR as u8);
+    }
+    bytes.extend_from_slice(b"..");
+  }
+  if !suffix.is_empty() {
+    if bytes.len() > start {
+      bytes.push(std::path::MAIN_SEPARATOR as u8);
+    }
+    bytes.extend_from_slice(suffix.as_bytes());
+  }
+}
+
 #[cfg(any(test, target_family = "windows"))]
 fn replace_forward_separator_in_owned(string: String) -> String {
   let mut bytes = string.into_bytes();
@@ -336,7 +375,7 @@ fn push_windows_relative_component(path: &mut OsString, component: &OsStr) {
 }
 
 #[cfg(target_family = "windows")]
-fn push_windows_path_component(
+pub(crate) fn push_windows_path_component(
   path: &mut OsString,
   component: &OsStr,
   forward_slash_is_separator: bool,
@@ -526,6 +565,144 @@ fn normalize_owned_for_resolution(path: PathBuf) -> PathBuf {
   normalize_owned_path_buf_with(path, TrailingSeparator::Strip)
 }
 
+/// Joins a relative receiver onto an absolute `cwd` like `PathBuf::push`,
+/// writing `cwd` in front of the receiver's bytes when its storage can hold
+/// the joined path.
+fn join_owned_onto_cwd<P>(path: PathBuf, cwd: P) -> PathBuf
+where
+  P: AsRef<Path> + Into<PathBuf>,
+{
+  let cwd_bytes = cwd.as_ref().as_os_str().as_encoded_bytes();
+  // An empty receiver resolves to the cwd itself, so it needs no separator.
+  let needs_separator = !path.as_os_str().is_empty()
+    && cwd_bytes.last().is_some_and(|&byte| !std::path::is_separator(char::from(byte)));
+  let joined_len = cwd_bytes.len() + usize::from(needs_separator) + path.as_os_str().len();
+
+  // Rooted receivers replace the cwd's root, and pushing onto a verbatim cwd
+  // resolves `.` and `..` as it joins; both keep std's implementation.
+  #[cfg(target_family = "windows")]
+  let push_rewrites = path.has_root()
+    || matches!(
+      cwd.as_ref().components().next(),
+      Some(Component::Prefix(prefix)) if windows_prefix_is_verbatim(prefix.kind())
+    );
+  #[cfg(not(target_family = "windows"))]
+  let push_rewrites = path.has_root();
+  if push_rewrites || path.capacity() < joined_len {
+    let mut resolved: PathBuf = cwd.into();
+    resolved.push(path);
+    return resolved;
+  }
+
+  let mut bytes = path.into_os_string().into_encoded_bytes();
+  let separator = needs_separator.then_some(std::path::MAIN_SEPARATOR as u8);
+  bytes.splice(0..0, cwd_bytes.iter().copied().chain(separator));
+  // SAFETY: the bytes are the encoded `cwd`, optionally an ASCII separator,
+  // and the encoded receiver, joined at boundaries that `OsStr` allows.
+  PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes) })
+}
+
+pub(crate) fn absolutize_owned_path_buf_with<P>(path: PathBuf, cwd: P) -> PathBuf
+where
+  P: AsRef<Path> + Into<PathBuf>,
+{
+  if path.is_absolute() {
+    return normalize_owned_for_resolution(path);
+  }
+
+  assert!(cwd.as_ref().is_absolute(), "explicit current directory must be absolute");
+
+  #[cfg(target_family = "windows")]
+  if let Some((drive, _)) = classify_drive_relative(&path) {
+    return absolutize_drive_relative_with(&path, cwd, drive).into_owned();
+  }
+
+  normalize_owned_for_resolution(join_owned_onto_cwd(path, cwd))
+}
+
+pub(crate) fn try_absolutize_owned_path_buf(path: PathBuf) -> io::Result<PathBuf> {
+  if path.is_absolute() {
+    return Ok(normalize_owned_for_resolution(path));
+  }
+
+  // Drive-relative paths resolve against the per-drive cwd, not the receiver.
+  #[cfg(target_family = "windows")]
+  if classify_drive_relative(&path).is_some() {
+    return path.try_absolutize().map(Cow::into_owned);
+  }
+
+  let cwd = try_get_current_dir()?;
+  Ok(absolutize_owned_path_buf_with(path, cwd))
+}
+
+/// Locates a borrowed relative result within `receiver`, or returns the result
+/// as an owned path.
+fn relative_range_in(
+  receiver: &Path,
+  outcome: RelativeOutcome<'_>,
+) -> Result<Range<usize>, PathBuf> {
+  let relative = match outcome {
+    RelativeOutcome::BorrowedNative(relative) => relative,
+    outcome => return Err(outcome.into_path_buf()),
+  };
+  let bytes = receiver.as_os_str().as_encoded_bytes();
+  let relative_bytes = relative.as_os_str().as_encoded_bytes();
+  match relative_bytes.as_ptr().addr().checked_sub(bytes.as_ptr().addr()) {
+    Some(start) if start + relative_bytes.len() <= bytes.len() => {
+      Ok(start..start + relative_bytes.len())
+    }
+    _ => Err(relative.to_owned()),
+  }
+}
+
+pub(crate) fn relative_owned_path_buf_with<P>(path: PathBuf, base: &Path, cwd: P) -> PathBuf
+where
+  P: AsRef<Path> + Into<PathBuf>,
+{
+  let range = match relative_range_in(&path, relative_outcome_with(&path, base, cwd)) {
+    Ok(range) => range,
+    Err(relative) => return relative,
+  };
+
+  let mut bytes = path.into_os_string().into_encoded_bytes();
+  bytes.truncate(range.end);
+  bytes.drain(..range.start);
+  // SAFETY: the range was borrowed from these bytes as a `Path`, so both ends
+  // are boundaries that `OsStr` allows.
+  PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes) })
+}
+
+/// Converts a lexical result of a UTF-8 receiver back to a `String`.
+///
+/// Lexical operations copy whole components of their input and add ASCII
+/// separators, `.`, and `..`, so the result is UTF-8 when the input is.
+fn lexical_path_buf_into_string(path: PathBuf) -> String {
+  let bytes = path.into_os_string().into_encoded_bytes();
+  debug_assert!(std::str::from_utf8(&bytes).is_ok(), "a lexical result of UTF-8 input is UTF-8");
+  // SAFETY: see above.
+  unsafe { String::from_utf8_unchecked(bytes) }
+}
+
+pub(crate) fn normalize_owned_string(string: String) -> String {
+  lexical_path_buf_into_string(normalize_owned_path_buf(PathBuf::from(string)))
+}
+
+pub(crate) fn relative_owned_string(mut string: String, base: &Path) -> String {
+  let outcome = try_relative_outcome(Path::new(&string), base)
+    .expect("failed to resolve relative paths against the current directory");
+  match relative_range_in(Path::new(&string), outcome) {
+    Ok(range) => {
+      string.truncate(range.end);
+      string.drain(..range.start);
+      string
+    }
+    // Parts of `base` or the cwd can reach an owned result.
+    Err(relative) => {
+      relative.into_os_string().into_string().expect("relative path is not valid Unicode")
+    }
+  }
+}
+
 fn normalize_path(path: &Path, trailing: TrailingSeparator) -> Cow<'_, Path> {
   if !needs_normalization(path, trailing) {
     return Cow::Borrowed(path);
@@ -534,23 +711,97 @@ fn normalize_path(path: &Path, trailing: TrailingSeparator) -> Cow<'_, Path> {
     path.components().peekable(),
     path.as_os_str().len(),
     trailing == TrailingSeparator::Preserve && has_trailing_separator(path),
-    {
-      #[cfg(target_family = "windows")]
-      {
-        windows_drive_spelling(path)
-      }
-      #[cfg(not(target_family = "windows"))]
-      {
-        None
-      }
-    },
+    native_drive_spelling(path),
   )
 }
 
-fn normalize_for_resolution(path: &Path) -> Cow<'_, Path> {
+/// Writes the [`SugarPath::normalize`] spelling of `path` into the empty `buf`.
+pub(crate) fn write_normalized_path(buf: &mut impl PathBytes, path: &Path) {
+  if !needs_normalization(path, TrailingSeparator::Preserve) {
+    buf.extend_from_slice(path.as_os_str().as_encoded_bytes());
+    return;
+  }
+  let preserve_trailing = has_trailing_separator(path);
+  write_normalized(
+    buf,
+    path.components().peekable(),
+    path.as_os_str().len(),
+    preserve_trailing,
+    native_drive_spelling(path),
+  );
+  if buf.is_empty() {
+    buf.push(b'.');
+    if preserve_trailing {
+      buf.push(std::path::MAIN_SEPARATOR as u8);
+    }
+  }
+}
+
+fn native_drive_spelling(_path: &Path) -> Option<u8> {
+  #[cfg(target_family = "windows")]
+  {
+    windows_drive_spelling(_path)
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    None
+  }
+}
+
+pub(crate) fn normalize_for_resolution(path: &Path) -> Cow<'_, Path> {
   normalize_path(path, TrailingSeparator::Strip)
 }
 
+/// How a lexical parent step treats a normalized path that has no trailing
+/// normal component left to remove.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParentStep {
+  /// Append one more unresolved `..`, as `lexical_parent` does for `.` and `..`.
+  Ascend,
+  /// Stop, so ancestor iteration ends after the last normal component.
+  Stop,
+}
+
+/// Return the lexical parent of an already normalized path.
+///
+/// A trailing normal component is removed by slicing, so a borrowed input
+/// yields a borrowed prefix. Roots and prefix-only paths have no parent, which
+/// matches `normalize_inner` discarding `..` directly under a root.
+pub(crate) fn normalized_parent<'a>(
+  path: &Cow<'a, Path>,
+  step: ParentStep,
+) -> Option<Cow<'a, Path>> {
+  match path.components().next_back()? {
+    Component::Normal(_) => {}
+    Component::CurDir | Component::ParentDir => {
+      return (step == ParentStep::Ascend)
+        .then(|| Cow::Owned(normalize_owned_for_resolution(path.join(".."))));
+    }
+    Component::RootDir | Component::Prefix(_) => return None,
+  }
+
+  Some(match path {
+    Cow::Borrowed(path) => parent_of_normal_component(path),
+    Cow::Owned(path) => Cow::Owned(parent_of_normal_component(path).into_owned()),
+  })
+}
+
+fn parent_of_normal_component(path: &Path) -> Cow<'_, Path> {
+  let parent = path.parent().expect("a path ending in a normal component has a parent");
+  if parent.as_os_str().is_empty() {
+    return Cow::Borrowed(Path::new("."));
+  }
+
+  // The standard parent of `C:foo` is the bare drive `C:`, which normalization
+  // spells as the drive-relative current directory `C:.`.
+  #[cfg(target_family = "windows")]
+  if !parent.has_root() && matches!(parent.components().next_back(), Some(Component::Prefix(_))) {
+    return normalize_for_resolution(parent);
+  }
+
+  Cow::Borrowed(parent)
+}
+
 #[inline]
 fn has_trailing_separator(path: &Path) -> bool {
   let Some(last) = path.as_os_str().as_encoded_bytes().last() else {
@@ -575,7 +826,7 @@ fn has_trailing_separator(path: &Path) -> bool {
   }
 }
 
-fn replace_main_separator_in_owned(mut string: String) -> String {
+pub(crate) fn replace_main_separator_in_owned(mut string: String) -> String {
   if std::path::MAIN_SEPARATOR == '/' {
     string
   } else {
@@ -590,6 +841,38 @@ fn replace_main_separator_in_owned(mut string: String) -> String {
   }
 }
 
+fn normalize_into_path_buf(path: &Path, out: &mut PathBuf) {
+  let mut bytes = std::mem::take(out).into_os_string().into_encoded_bytes();
+  bytes.clear();
+  // Normalization only appends `.` and a separator to clean input.
+  bytes.reserve(path.as_os_str().len() + 2);
+  write_normalized_path(&mut bytes, path);
+  // SAFETY: `write_normalized_path` writes whole components of `path` and
+  // ASCII separators, `.`, and `..`, which keeps the encoding valid.
+  *out = PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes) });
+}
+
+fn upward_relative_into_path_buf(ups: usize, suffix: &str, out: &mut PathBuf) {
+  let mut bytes = std::mem::take(out).into_os_string().into_encoded_bytes();
+  bytes.clear();
+  bytes.reserve(ups * 3 + suffix.len());
+  write_upward_relative(&mut bytes, ups, suffix);
+  // SAFETY: see `RelativeOutcome::into_path_buf`.
+  *out = PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(bytes) });
+}
+
+fn replace_path_buf_contents(out: &mut PathBuf, path: &Path) {
+  let out = out.as_mut_os_string();
+  out.clear();
+  out.push(path);
+}
+
+fn to_slash_into_string(text: Option<&str>, out: &mut String) {
+  out.clear();
+  out.push_str(text.expect("path is not valid Unicode"));
+  *out = replace_main_separator_in_owned(std::mem::take(out));
+}
+
 pub(crate) fn try_path_buf_into_slash(path: PathBuf) -> Result<String, PathBuf> {
   match path.into_os_string().into_string() {
     Ok(string) => Ok(replace_main_separator_in_owned(string)),
@@ -635,12 +918,6 @@ fn windows_standalone_relative_is_representable(path: &str) -> bool {
   windows_standalone_relative_bytes_are_representable(path.as_bytes())
 }
 
-#[cfg(target_family = "windows")]
-fn windows_standalone_relative_bytes_are_representable(path: &[u8]) -> bool {
-  !matches!(path.first(), Some(b'/' | b'\\'))
-    && !(path.len() >= 2 && path[0].is_ascii_alphabetic() && path[1] == b':')
-}
-
 #[cfg(target_family = "windows")]
 fn windows_relative_component_has_literal_slash(component: &Component<'_>) -> bool {
   let Component::Normal(component) = component else {
@@ -717,18 +994,7 @@ fn relative_windows_native_fast<'a>(target: &'a str, base: &str) -> Option<Relat
     return Some(RelativeOutcome::BorrowedNative(Path::new(target_suffix)));
   }
 
-  let mut relative = String::with_capacity(ups * 3 + target_suffix.len());
-  for _ in 0..ups {
-    if !relative.is_empty() {
-      relative.push('\\');
-    }
-    relative.push_str("..");
-  }
-  if !target_suffix.is_empty() {
-    relative.push('\\');
-    relative.push_str(target_suffix);
-  }
-  Some(RelativeOutcome::Native(PathBuf::from(relative)))
+  Some(RelativeOutcome::Upward { ups, suffix: target_suffix })
 }
 
 #[cfg(target_family = "windows")]
@@ -761,7 +1027,7 @@ fn try_relative_windows_absolute<'a>(
   // fallback. Its temporary allocations stay off the canonical Rolldown path.
   let target_fwd = normalize_backslash_cow(target_str);
   let base_fwd = normalize_backslash_cow(base_str);
-  let relative = relative_str(&target_fwd, &base_fwd);
+  let relative = relative_str(&target_fwd, &base_fwd).into_cow();
   if !windows_standalone_relative_is_representable(&relative) {
     return None;
   }
@@ -815,8 +1081,9 @@ fn relative_without_cwd<'a>(
     && let (Some(target_str), Some(base_str)) = (target_path.to_str(), base_path.to_str())
   {
     return Some(match relative_str(target_str, base_str) {
-      Cow::Borrowed(relative) => RelativeOutcome::BorrowedNative(Path::new(relative)),
-      Cow::Owned(relative) => RelativeOutcome::Slash(relative),
+      RelativeStr::Upward { ups: 0, suffix } => RelativeOutcome::BorrowedNative(Path::new(suffix)),
+      RelativeStr::Upward { ups, suffix } => RelativeOutcome::Upward { ups, suffix },
+      RelativeStr::Normalized(relative) => RelativeOutcome::Slash(relative),
     });
   }
 
@@ -843,6 +1110,20 @@ fn relative_without_cwd<'a>(
   None
 }
 
+/// Compare two components with the host's path comparison rules: exact on
+/// Unix and WASI, ASCII case-insensitive for Windows roots and components.
+#[inline]
+pub(crate) fn components_eq(from: &Component<'_>, to: &Component<'_>) -> bool {
+  #[cfg(target_family = "windows")]
+  {
+    windows_components_eq_ignore_ascii_case(from, to)
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    from == to
+  }
+}
+
 fn relative_from_resolved(base: Cow<'_, Path>, target: Cow<'_, Path>) -> RelativeOutcome<'static> {
   #[cfg(target_family = "windows")]
   if windows_paths_have_different_prefixes(base.as_ref(), target.as_ref()) {
@@ -861,16 +1142,7 @@ fn relative_from_resolved(base: Cow<'_, Path>, target: Cow<'_, Path>) -> Relativ
   let common_len = base_components
     .clone()
     .zip(target_components.clone())
-    .take_while(|(from, to)| {
-      #[cfg(target_family = "windows")]
-      {
-        windows_components_eq_ignore_ascii_case(from, to)
-      }
-      #[cfg(not(target_family = "windows"))]
-      {
-        from == to
-      }
-    })
+    .take_while(|(from, to)| components_eq(from, to))
     .count();
   let up_len = base_components.count().saturating_sub(common_len);
   #[cfg(target_family = "windows")]
@@ -957,7 +1229,7 @@ fn try_relative_outcome<'a>(
   Ok(relative_from_resolved(base, target))
 }
 
-fn relative_outcome_with<'a, P>(
+pub(crate) fn relative_outcome_with<'a, P>(
   target_path: &'a Path,
   base_path: &Path,
   cwd: P,
@@ -1001,6 +1273,10 @@ impl SugarPath for Path {
     normalize_path(self, TrailingSeparator::Preserve)
   }
 
+  fn normalize_into(&self, out: &mut PathBuf) {
+    normalize_into_path_buf(self, out);
+  }
+
   fn absolutize(&self) -> Cow<'_, Path> {
     self.try_absolutize().expect("failed to resolve path against the current directory")
   }
@@ -1055,10 +1331,24 @@ impl SugarPath for Path {
     relative_outcome_with(self, base.as_ref(), cwd).into_cow_path()
   }
 
+  fn relative_into(&self, base: impl AsRef<Path>, out: &mut PathBuf) {
+    match try_relative_outcome(self, base.as_ref())
+      .expect("failed to resolve relative paths against the current directory")
+    {
+      RelativeOutcome::BorrowedNative(relative) => replace_path_buf_contents(out, relative),
+      RelativeOutcome::Upward { ups, suffix } => upward_relative_into_path_buf(ups, suffix, out),
+      outcome => replace_path_buf_contents(out, &outcome.into_path_buf()),
+    }
+  }
+
   fn to_slash(&self) -> Cow<'_, str> {
     self.try_to_slash().expect("path is not valid Unicode")
   }
 
+  fn to_slash_into(&self, out: &mut String) {
+    to_slash_into_string(self.to_str(), out);
+  }
+
   fn try_to_slash(&self) -> Option<Cow<'_, str>> {
     if std::path::MAIN_SEPARATOR == '/' {
       self.to_str().map(Cow::Borrowed)
@@ -1090,6 +1380,100 @@ impl SugarPath for Path {
   fn as_path(&self) -> &Path {
     self
   }
+
+  fn lexical_parent(&self) -> Option<Cow<'_, Path>> {
+    normalized_parent(&self.normalize(), ParentStep::Ascend)
+  }
+
+  fn lexical_ancestors(&self) -> LexicalAncestors<'_> {
+    LexicalAncestors::new(self.normalize())
+  }
+
+  fn portability_issues(&self, targets: PortabilityTargets) -> Vec<PortabilityIssue> {
+    crate::portability::portability_issues(self, targets)
+  }
+
+  fn expand_with(&self, env: &ExpandEnv) -> Result<Cow<'_, Path>, ExpandError> {
+    crate::expand::expand_with(self, env)
+  }
+
+  fn expand(&self) -> Result<Cow<'_, Path>, ExpandError> {
+    crate::expand::expand_ambient(self)
+  }
+
+  fn contract_home(&self, home: impl AsRef<Path>) -> Cow<'_, Path> {
+    contract_home_prefix(self, home.as_ref()).map_or_else(|| self.normalize(), Cow::Owned)
+  }
+
+  fn contract_home_with(
+    &self,
+    home: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'_, Path> {
+    shortest_display(self, Some(home.as_ref()), cwd.as_ref())
+  }
+
+  fn split_extension_with<S: AsRef<OsStr>>(&self, known: &[S]) -> Option<(&OsStr, &OsStr)> {
+    crate::extension::split_extension_with(self, known)
+  }
+
+  fn full_extension(&self) -> Option<&OsStr> {
+    crate::extension::full_extension(self)
+  }
+
+  fn with_full_extension(&self, extension: impl AsRef<OsStr>) -> PathBuf {
+    crate::extension::with_full_extension(self, extension.as_ref())
+  }
+
+  #[cfg(target_family = "windows")]
+  fn to_verbatim(&self) -> Cow<'_, Path> {
+    crate::verbatim::to_verbatim(self)
+  }
+
+  #[cfg(target_family = "windows")]
+  fn strip_verbatim(&self) -> Cow<'_, Path> {
+    crate::verbatim::strip_verbatim(self)
+  }
+}
+
+/// Returns `path` with a leading `home` replaced by `~`. An empty or relative
+/// `home`, such as an unset `HOME` read as `""`, contracts nothing.
+pub(crate) fn contract_home_prefix(path: &Path, home: &Path) -> Option<PathBuf> {
+  if !home.is_absolute() {
+    return None;
+  }
+  replace_prefix(&normalize_for_resolution(path), &normalize_for_resolution(home), Path::new("~"))
+}
+
+/// Returns `path` relative to `cwd`, spelled `.` when they are equal.
+pub(crate) fn relative_display<'a>(path: &'a Path, cwd: &Path) -> Cow<'a, Path> {
+  match path.relative_with(cwd, cwd) {
+    relative if relative.as_os_str().is_empty() => Cow::Borrowed(Path::new(".")),
+    relative => relative,
+  }
+}
+
+/// Returns the shortest of the `~`-contracted, cwd-relative, and absolute
+/// spellings of `path`. Ties favor the contracted spelling, then the relative
+/// one.
+pub(crate) fn shortest_display<'a>(
+  path: &'a Path,
+  home: Option<&Path>,
+  cwd: &Path,
+) -> Cow<'a, Path> {
+  let absolute = path.absolutize_with(cwd);
+  let contracted = home.and_then(|home| contract_home_prefix(&absolute, home));
+  let relative = relative_display(path, cwd);
+
+  let len = |path: &Path| path.as_os_str().len();
+  let mut shortest = absolute;
+  if len(&relative) <= len(&shortest) {
+    shortest = relative;
+  }
+  match contracted {
+    Some(contracted) if len(&contracted) <= len(&shortest) => Cow::Owned(contracted),
+    _ => shortest,
+  }
 }
 
 /// Check whether a path needs normalization. Returns `false` for already-clean
@@ -1109,61 +1493,7 @@ fn needs_normalization(path: &Path, trailing: TrailingSeparator) -> bool {
   let bytes = path.as_os_str().as_encoded_bytes();
   // OsStr's encoded representation is self-synchronizing and preserves ASCII,
   // so native separators and dot components cannot hide inside another unit.
-  let separator = std::path::MAIN_SEPARATOR as u8;
-  if bytes.is_empty() {
-    return true;
-  }
-  if bytes == b"." || (trailing == TrailingSeparator::Preserve && bytes == [b'.', separator]) {
-    return false;
-  }
-  // A leading `.` needs normalization. A leading run of `..` can be clean when
-  // every remaining component is normal (`...` and `.foo` are normal names).
-  if bytes[0] == b'.' {
-    if bytes.len() == 1 || bytes[1] == separator {
-      return true;
-    }
-    if bytes[1] == b'.' && (bytes.len() == 2 || bytes[2] == separator) {
-      return !leading_parent_path_is_normalized(
-        bytes,
-        separator,
-        trailing == TrailingSeparator::Preserve,
-      );
-    }
-  }
-  // Trailing separator (unless the path is exactly the root separator).
-  if trailing == TrailingSeparator::Strip && bytes.len() > 1 && bytes[bytes.len() - 1] == separator
-  {
-    return true;
-  }
-  // Scan for duplicate separators and dot components.
-  let mut offset = 0;
-  while let Some(pos) = memchr(separator, &bytes[offset..]) {
-    let slash = offset + pos;
-    let next = slash + 1;
-    if next < bytes.len() {
-      let b = bytes[next];
-      // `//` — consecutive slashes
-      if b == separator {
-        return true;
-      }
-      // `/.` — could be `/.` or `/..`
-      if b == b'.' {
-        let after_dot = next + 1;
-        // "/." at end or "/./"
-        if after_dot >= bytes.len() || bytes[after_dot] == separator {
-          return true;
-        }
-        // "/.." at end or "/../"
-        if bytes[after_dot] == b'.'
-          && (after_dot + 1 >= bytes.len() || bytes[after_dot + 1] == separator)
-        {
-          return true;
-        }
-      }
-    }
-    offset = next;
-  }
-  false
+  unix_bytes_need_normalization(bytes, std::path::MAIN_SEPARATOR as u8, trailing)
 }
 
 /// Check whether a path needs normalization (Windows variant).
@@ -1172,158 +1502,50 @@ fn needs_normalization(path: &Path, trailing: TrailingSeparator) -> bool {
 fn needs_normalization(path: &Path, trailing: TrailingSeparator) -> bool {
   // OsStr's encoded representation is self-synchronizing and preserves ASCII,
   // so separators and dot components cannot hide inside an encoded unit.
-  let bytes = path.as_os_str().as_encoded_bytes();
-  if bytes.is_empty() {
-    return true;
-  }
-  if bytes == b"." || (trailing == TrailingSeparator::Preserve && bytes == b".\\") {
-    return false;
-  }
-  // Any forward slash means normalization is needed (gets converted to `\`)
-  if memchr(b'/', bytes).is_some() {
-    return true;
-  }
-  // UNC prefix `\\` at start — always bail out to normalizer
-  if bytes.len() >= 2 && bytes[0] == b'\\' && bytes[1] == b'\\' {
-    return true;
-  }
-  // A bare drive `X:` normalizes to `X:.`. Other drive-relative paths keep
-  // their drive spelling and lexical form.
-  if bytes.len() == 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic() {
-    return true; // bare `C:`
-  }
-  // `C:.` and `C:.\` are canonical drive-relative current-directory
-  // spellings, but the same component before another segment is redundant.
-  if bytes.len() > 4
-    && bytes[1] == b':'
-    && bytes[0].is_ascii_alphabetic()
-    && bytes[2] == b'.'
-    && bytes[3] == b'\\'
-  {
-    return true;
-  }
-  // A drive-relative path can keep unresolved leading parents. Skip the
-  // generic `\..` rejection when the entire tail is already in canonical
-  // leading-parent form, such as `C:..\..\foo`.
-  if bytes.len() >= 4
-    && bytes[1] == b':'
-    && bytes[0].is_ascii_alphabetic()
-    && bytes[2] == b'.'
-    && bytes[3] == b'.'
-    && (bytes.len() == 4 || bytes[4] == b'\\')
-  {
-    return !leading_parent_path_is_normalized(
-      &bytes[2..],
-      b'\\',
-      trailing == TrailingSeparator::Preserve,
-    );
-  }
-  // A leading `.` needs normalization. A leading run of `..` can be clean when
-  // every remaining component is normal (`...` and `.foo` are normal names).
-  if bytes[0] == b'.' {
-    if bytes.len() == 1 || bytes[1] == b'\\' {
-      return true;
-    }
-    if bytes[1] == b'.' && (bytes.len() == 2 || bytes[2] == b'\\') {
-      return !leading_parent_path_is_normalized(
-        bytes,
-        b'\\',
-        trailing == TrailingSeparator::Preserve,
-      );
-    }
-  }
-  // Trailing `\` (unless path is `\` alone or `X:\`)
-  if trailing == TrailingSeparator::Strip && bytes[bytes.len() - 1] == b'\\' {
-    // `\` alone is clean
-    if bytes.len() == 1 {
-      return false;
-    }
-    // `X:\` is clean
-    if bytes.len() == 3 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic() {
-      return false;
-    }
-    return true;
-  }
-  // memchr scan for `\\` (consecutive), `\.`, `\..`
-  let mut offset = 0;
-  while let Some(pos) = memchr(b'\\', &bytes[offset..]) {
-    let slash = offset + pos;
-    let next = slash + 1;
-    if next < bytes.len() {
-      let b = bytes[next];
-      // `\\` — consecutive separators
-      if b == b'\\' {
-        return true;
-      }
-      // `\.` — could be `\.` or `\..`
-      if b == b'.' {
-        let after_dot = next + 1;
-        // "\." at end or "\.\"
-        if after_dot >= bytes.len() || bytes[after_dot] == b'\\' {
-          return true;
-        }
-        // "\.." at end or "\..\"
-        if bytes[after_dot] == b'.'
-          && (after_dot + 1 >= bytes.len() || bytes[after_dot + 1] == b'\\')
-        {
-          return true;
-        }
-      }
-    }
-    offset = next;
-  }
-  false
+  windows_bytes_need_normalization(path.as_os_str().as_encoded_bytes(), trailing)
 }
 
-/// Return whether an unprefixed path starting with a `..` component is already
-/// in the exact spelling produced by `normalize_inner`.
-///
-/// A canonical path may contain one or more leading `..` components followed
-/// only by normal components. Empty components, `.`, a `..` after any normal
-/// component, and a trailing separator all require normalization.
 #[inline]
-fn leading_parent_path_is_normalized(bytes: &[u8], separator: u8, preserve_trailing: bool) -> bool {
-  debug_assert!(bytes == b".." || bytes.starts_with(&[b'.', b'.', separator]));
+fn normalize_inner<'a>(
+  components: Peekable<impl Iterator<Item = Component<'a>>>,
+  hint_cap: usize,
+  preserve_trailing: bool,
+  drive_spelling: Option<u8>,
+) -> Cow<'a, Path> {
+  let mut buf: Vec<u8> = Vec::with_capacity(hint_cap);
+  write_normalized(&mut buf, components, hint_cap, preserve_trailing, drive_spelling);
 
-  let mut offset = 0;
-  let mut saw_normal = false;
-  loop {
-    let end =
-      memchr(separator, &bytes[offset..]).map(|position| offset + position).unwrap_or(bytes.len());
-    let component = &bytes[offset..end];
-
-    if component == b".." {
-      if saw_normal {
-        return false;
-      }
-    } else if component.is_empty() || component == b"." {
-      return false;
-    } else {
-      saw_normal = true;
-    }
-
-    if end == bytes.len() {
-      return true;
-    }
-    offset = end + 1;
-    if offset == bytes.len() {
-      return preserve_trailing;
+  // --- Empty result → "." ---
+  if buf.is_empty() {
+    if preserve_trailing {
+      let mut current_directory = PathBuf::from(".");
+      current_directory.push("");
+      return Cow::Owned(current_directory);
     }
+    return Cow::Borrowed(Path::new("."));
   }
+
+  // SAFETY: `buf` was built entirely from:
+  // - encoded bytes of OsStr components (valid platform encoding)
+  // - ASCII separator bytes and ASCII '.' characters
+  // This preserves the encoding invariants required by OsString.
+  Cow::Owned(PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(buf) }))
 }
 
-#[inline]
-fn normalize_inner<'a>(
+/// Writes the normalized spelling of `components` into the empty `buf`.
+///
+/// `_path_len` is the encoded length of the whole input path. An empty result
+/// leaves `buf` empty for the caller to spell as the current directory.
+pub(crate) fn write_normalized<'a>(
+  buf: &mut impl PathBytes,
   mut components: Peekable<impl Iterator<Item = Component<'a>>>,
-  hint_cap: usize,
+  _path_len: usize,
   preserve_trailing: bool,
   _drive_spelling: Option<u8>,
-) -> Cow<'a, Path> {
+) {
+  debug_assert!(buf.is_empty());
   let sep_byte = std::path::MAIN_SEPARATOR as u8;
-  let mut buf: Vec<u8> = Vec::with_capacity(hint_cap);
   let mut has_root = false;
-  let mut depth: usize = 0; // count of Normal segments currently in buf
-  let mut need_sep = false;
 
   // --- Prefix (Windows only) ---
   #[cfg(target_family = "windows")]
@@ -1379,7 +1601,7 @@ fn normalize_inner<'a>(
   // --- RootDir ---
   if matches!(components.peek(), Some(Component::RootDir)) {
     #[cfg(target_family = "windows")]
-    let prefix_has_synthetic_root = buf.len() == hint_cap && prefix_root_is_optional;
+    let prefix_has_synthetic_root = buf.len() == _path_len && prefix_root_is_optional;
     #[cfg(not(target_family = "windows"))]
     let prefix_has_synthetic_root = false;
     if !prefix_has_synthetic_root {
@@ -1392,45 +1614,22 @@ fn normalize_inner<'a>(
   let root_end = buf.len();
 
   // --- Remaining components ---
-  for component in components {
-    match component {
+  let _depth = push_lexical_components(
+    buf,
+    root_end,
+    has_root,
+    sep_byte,
+    components.map(|component| match component {
       Component::Prefix(prefix) => unreachable!("Unexpected prefix for {:?}", prefix),
       Component::RootDir => unreachable!("Unexpected RootDir after initial position"),
-      Component::CurDir => {}
-      Component::ParentDir => {
-        if depth > 0 {
-          // Roll back the last Normal segment using memrchr.
-          let search_region = &buf[root_end..];
-          if let Some(pos) = memrchr(sep_byte, search_region) {
-            buf.truncate(root_end + pos);
-          } else {
-            buf.truncate(root_end);
-          }
-          depth -= 1;
-          need_sep = buf.len() > root_end;
-        } else if !has_root {
-          // Relative path going above start: write ".." literally
-          if need_sep {
-            buf.push(sep_byte);
-          }
-          buf.extend_from_slice(b"..");
-          need_sep = true;
-        }
-        // else: has_root && depth == 0 → ignore (can't go above root)
-      }
-      Component::Normal(s) => {
-        if need_sep {
-          buf.push(sep_byte);
-        }
-        buf.extend_from_slice(s.as_encoded_bytes());
-        depth += 1;
-        need_sep = true;
-      }
-    }
-  }
+      Component::CurDir => LexicalComponent::CurDir,
+      Component::ParentDir => LexicalComponent::ParentDir,
+      Component::Normal(s) => LexicalComponent::Normal(s.as_encoded_bytes()),
+    }),
+  );
 
   #[cfg(target_family = "windows")]
-  if prefix_root_is_optional && depth == 0 && !preserve_trailing {
+  if prefix_root_is_optional && _depth == 0 && !preserve_trailing {
     buf.truncate(prefix_len);
   }
 
@@ -1438,23 +1637,13 @@ fn normalize_inner<'a>(
   // a standalone Windows path. Keep the minimal `.\` spelling when removing
   // earlier lexical components would otherwise change that component's type.
   #[cfg(target_family = "windows")]
-  if prefix_len == 0 && !has_root && !windows_standalone_relative_bytes_are_representable(&buf) {
-    let len = buf.len();
-    buf.reserve(2);
-    buf.resize(len + 2, 0);
-    buf.copy_within(0..len, 2);
-    buf[0] = b'.';
-    buf[1] = sep_byte;
+  if prefix_len == 0 && !has_root && !windows_standalone_relative_bytes_are_representable(&buf[..])
+  {
+    buf.insert_front(&[b'.', sep_byte]);
   }
 
-  // --- Empty result → "." ---
   if buf.is_empty() {
-    if preserve_trailing {
-      let mut current_directory = PathBuf::from(".");
-      current_directory.push("");
-      return Cow::Owned(current_directory);
-    }
-    return Cow::Borrowed(Path::new("."));
+    return;
   }
 
   // --- Prefix-only: preserve its component semantics or use its canonical suffix ---
@@ -1469,12 +1658,6 @@ fn normalize_inner<'a>(
   if preserve_trailing && buf.last() != Some(&sep_byte) {
     buf.push(sep_byte);
   }
-
-  // SAFETY: `buf` was built entirely from:
-  // - encoded bytes of OsStr components (valid platform encoding)
-  // - ASCII separator bytes and ASCII '.' characters
-  // This preserves the encoding invariants required by OsString.
-  Cow::Owned(PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(buf) }))
 }
 
 impl SugarPath for str {
@@ -1482,6 +1665,10 @@ impl SugarPath for str {
     Path::new(self).normalize()
   }
 
+  fn normalize_into(&self, out: &mut PathBuf) {
+    Path::new(self).normalize_into(out);
+  }
+
   fn absolutize(&self) -> Cow<'_, Path> {
     Path::new(self).absolutize()
   }
@@ -1510,6 +1697,10 @@ impl SugarPath for str {
     Path::new(self).relative_with(base, cwd)
   }
 
+  fn relative_into(&self, base: impl AsRef<Path>, out: &mut PathBuf) {
+    Path::new(self).relative_into(base, out);
+  }
+
   fn to_slash(&self) -> Cow<'_, str> {
     if std::path::MAIN_SEPARATOR == '/' {
       Cow::Borrowed(self)
@@ -1529,9 +1720,183 @@ impl SugarPath for str {
     self.to_slash()
   }
 
+  fn to_slash_into(&self, out: &mut String) {
+    to_slash_into_string(Some(self), out);
+  }
+
   fn as_path(&self) -> &Path {
     Path::new(self)
   }
+
+  fn lexical_parent(&self) -> Option<Cow<'_, Path>> {
+    Path::new(self).lexical_parent()
+  }
+
+  fn lexical_ancestors(&self) -> LexicalAncestors<'_> {
+    Path::new(self).lexical_ancestors()
+  }
+
+  fn portability_issues(&self, targets: PortabilityTargets) -> Vec<PortabilityIssue> {
+    Path::new(self).portability_issues(targets)
+  }
+
+  fn expand_with(&self, env: &ExpandEnv) -> Result<Cow<'_, Path>, ExpandError> {
+    Path::new(self).expand_with(env)
+  }
+
+  fn expand(&self) -> Result<Cow<'_, Path>, ExpandError> {
+    Path::new(self).expand()
+  }
+
+  fn contract_home(&self, home: impl AsRef<Path>) -> Cow<'_, Path> {
+    Path::new(self).contract_home(home)
+  }
+
+  fn contract_home_with(
+    &self,
+    home: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'_, Path> {
+    Path::new(self).contract_home_with(home, cwd)
+  }
+
+  fn split_extension_with<S: AsRef<OsStr>>(&self, known: &[S]) -> Option<(&OsStr, &OsStr)> {
+    Path::new(self).split_extension_with(known)
+  }
+
+  fn full_extension(&self) -> Option<&OsStr> {
+    Path::new(self).full_extension()
+  }
+
+  fn with_full_extension(&self, extension: impl AsRef<OsStr>) -> PathBuf {
+    Path::new(self).with_full_extension(extension)
+  }
+
+  #[cfg(target_family = "windows")]
+  fn to_verbatim(&self) -> Cow<'_, Path> {
+    Path::new(self).to_verbatim()
+  }
+
+  #[cfg(target_family = "windows")]
+  fn strip_verbatim(&self) -> Cow<'_, Path> {
+    Path::new(self).strip_verbatim()
+  }
+}
+
+impl SugarPath for OsStr {
+  fn normalize(&self) -> Cow<'_, Path> {
+    Path::new(self).normalize()
+  }
+
+  fn normalize_into(&self, out: &mut PathBuf) {
+    Path::new(self).normalize_into(out);
+  }
+
+  fn absolutize(&self) -> Cow<'_, Path> {
+    Path::new(self).absolutize()
+  }
+
+  fn try_absolutize(&self) -> io::Result<Cow<'_, Path>> {
+    Path::new(self).try_absolutize()
+  }
+
+  fn absolutize_with(&self, cwd: impl AsRef<Path> + Into<PathBuf>) -> Cow<'_, Path> {
+    Path::new(self).absolutize_with(cwd)
+  }
+
+  fn relative(&self, base: impl AsRef<Path>) -> Cow<'_, Path> {
+    Path::new(self).relative(base)
+  }
+
+  fn try_relative(&self, base: impl AsRef<Path>) -> io::Result<Cow<'_, Path>> {
+    Path::new(self).try_relative(base)
+  }
+
+  fn relative_with(
+    &self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'_, Path> {
+    Path::new(self).relative_with(base, cwd)
+  }
+
+  fn relative_into(&self, base: impl AsRef<Path>, out: &mut PathBuf) {
+    Path::new(self).relative_into(base, out);
+  }
+
+  fn to_slash(&self) -> Cow<'_, str> {
+    Path::new(self).to_slash()
+  }
+
+  fn try_to_slash(&self) -> Option<Cow<'_, str>> {
+    Path::new(self).try_to_slash()
+  }
+
+  fn to_slash_lossy(&self) -> Cow<'_, str> {
+    Path::new(self).to_slash_lossy()
+  }
+
+  fn to_slash_into(&self, out: &mut String) {
+    Path::new(self).to_slash_into(out);
+  }
+
+  fn as_path(&self) -> &Path {
+    Path::new(self)
+  }
+
+  fn lexical_parent(&self) -> Option<Cow<'_, Path>> {
+    Path::new(self).lexical_parent()
+  }
+
+  fn lexical_ancestors(&self) -> LexicalAncestors<'_> {
+    Path::new(self).lexical_ancestors()
+  }
+
+  fn portability_issues(&self, targets: PortabilityTargets) -> Vec<PortabilityIssue> {
+    Path::new(self).portability_issues(targets)
+  }
+
+  fn expand_with(&self, env: &ExpandEnv) -> Result<Cow<'_, Path>, ExpandError> {
+    Path::new(self).expand_with(env)
+  }
+
+  fn expand(&self) -> Result<Cow<'_, Path>, ExpandError> {
+    Path::new(self).expand()
+  }
+
+  fn contract_home(&self, home: impl AsRef<Path>) -> Cow<'_, Path> {
+    Path::new(self).contract_home(home)
+  }
+
+  fn contract_home_with(
+    &self,
+    home: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'_, Path> {
+    Path::new(self).contract_home_with(home, cwd)
+  }
+
+  fn split_extension_with<S: AsRef<OsStr>>(&self, known: &[S]) -> Option<(&OsStr, &OsStr)> {
+    Path::new(self).split_extension_with(known)
+  }
+
+  fn full_extension(&self) -> Option<&OsStr> {
+    Path::new(self).full_extension()
+  }
+
+  fn with_full_extension(&self, extension: impl AsRef<OsStr>) -> PathBuf {
+    Path::new(self).with_full_extension(extension)
+  }
+
+  #[cfg(target_family = "windows")]
+  fn to_verbatim(&self) -> Cow<'_, Path> {
+    Path::new(self).to_verbatim()
+  }
+
+  #[cfg(target_family = "windows")]
+  fn strip_verbatim(&self) -> Cow<'_, Path> {
+    Path::new(self).strip_verbatim()
+  }
 }
 
 #[cfg(target_family = "windows")]
@@ -1583,8 +1948,39 @@ fn windows_prefixes_eq_ignore_ascii_case(
   }
 }
 
+/// A `/`-separated result of [`relative_str`].
+enum RelativeStr<'a> {
+  /// `ups` parent steps followed by `suffix`, a suffix of the target.
+  Upward {
+    ups: usize,
+    suffix: &'a str,
+  },
+  Normalized(String),
+}
+
+impl<'a> RelativeStr<'a> {
+  #[cfg(any(test, target_family = "windows"))]
+  fn into_cow(self) -> Cow<'a, str> {
+    match self {
+      Self::Upward { ups: 0, suffix } => Cow::Borrowed(suffix),
+      Self::Upward { ups, suffix } => {
+        let mut result = String::with_capacity(ups * 3 + suffix.len());
+        let suffix_iter = if suffix.is_empty() { None } else { Some(suffix) };
+        std::iter::repeat_n("..", ups).chain(suffix_iter).for_each(|s| {
+          if !result.is_empty() {
+            result.push('/');
+          }
+          result.push_str(s);
+        });
+        Cow::Owned(result)
+      }
+      Self::Normalized(relative) => Cow::Owned(relative),
+    }
+  }
+}
+
 #[cfg(all(target_os = "macos", target_arch = "aarch64", target_feature = "neon"))]
-fn relative_str<'a>(target: &'a str, base: &str) -> Cow<'a, str> {
+fn relative_str<'a>(target: &'a str, base: &str) -> RelativeStr<'a> {
   let target = target.trim_end_matches('/');
   let base = base.trim_end_matches('/');
   relative_str_suffix_validated(target, base)
@@ -1594,7 +1990,7 @@ fn relative_str<'a>(target: &'a str, base: &str) -> Cow<'a, str> {
   any(target_os = "macos", target_os = "linux"),
   any(test, all(target_os = "macos", target_arch = "aarch64", target_feature = "neon"))
 ))]
-fn relative_str_suffix_validated<'a>(target: &'a str, base: &str) -> Cow<'a, str> {
+fn relative_str_suffix_validated<'a>(target: &'a str, base: &str) -> RelativeStr<'a> {
   let common_byte_len = common_prefix_len_case_sensitive(target.as_bytes(), base.as_bytes());
   let at_boundary = (common_byte_len == target.len() && common_byte_len == base.len())
     || (common_byte_len == target.len() && base.as_bytes().get(common_byte_len) == Some(&b'/'))
@@ -1609,9 +2005,9 @@ fn relative_str_suffix_validated<'a>(target: &'a str, base: &str) -> Cow<'a, str
     // `base` is an exact component-boundary prefix of `target`, so every base
     // component and separator has already been validated when `target` is clean.
     if needs_relative_normalization(target) {
-      return Cow::Owned(relative_str_slow(target, base));
+      return RelativeStr::Normalized(relative_str_slow(target, base));
     }
-    return Cow::Borrowed(target[common_prefix..].trim_start_matches('/'));
+    return RelativeStr::Upward { ups: 0, suffix: target[common_prefix..].trim_start_matches('/') };
   }
 
   let base_remaining = &base.as_bytes()[common_prefix..];
@@ -1639,18 +2035,21 @@ fn relative_str_suffix_validated<'a>(target: &'a str, base: &str) -> Cow<'a, str
       || needs_relative_normalization(&base[common_prefix..])
   };
   if needs_normalization {
-    Cow::Owned(relative_str_slow(target, base))
+    RelativeStr::Normalized(relative_str_slow(target, base))
   } else {
-    relative_str_from_parts(target, common_prefix, ups as usize)
+    RelativeStr::Upward {
+      ups: ups as usize,
+      suffix: target[common_prefix..].trim_start_matches('/'),
+    }
   }
 }
 
 #[cfg(not(all(target_os = "macos", target_arch = "aarch64", target_feature = "neon")))]
-fn relative_str<'a>(target: &'a str, base: &str) -> Cow<'a, str> {
+fn relative_str<'a>(target: &'a str, base: &str) -> RelativeStr<'a> {
   let target = target.trim_end_matches('/');
   let base = base.trim_end_matches('/');
   if needs_relative_normalization(target) || needs_relative_normalization(base) {
-    Cow::Owned(relative_str_slow(target, base))
+    RelativeStr::Normalized(relative_str_slow(target, base))
   } else {
     relative_str_fast(target, base)
   }
@@ -1663,7 +2062,18 @@ fn common_prefix_len_case_sensitive(left: &[u8], right: &[u8]) -> usize {
   {
     common_prefix_len_neon(left, right)
   }
-  #[cfg(not(all(target_arch = "aarch64", target_feature = "neon")))]
+  #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+  {
+    common_prefix_len_avx2(left, right)
+  }
+  #[cfg(all(target_arch = "x86_64", target_feature = "sse2", not(target_feature = "avx2")))]
+  {
+    common_prefix_len_sse2(left, right)
+  }
+  #[cfg(not(any(
+    all(target_arch = "aarch64", target_feature = "neon"),
+    all(target_arch = "x86_64", target_feature = "sse2")
+  )))]
   {
     common_prefix_len_scalar(left, right)
   }
@@ -1713,9 +2123,64 @@ fn common_prefix_len_neon(left: &[u8], right: &[u8]) -> usize {
   offset + common_prefix_len_scalar(&left[offset..len], &right[offset..len])
 }
 
+#[cfg(all(not(target_family = "windows"), target_arch = "x86_64", target_feature = "sse2"))]
+#[inline]
+fn common_prefix_len_sse2(left: &[u8], right: &[u8]) -> usize {
+  use std::arch::x86_64::{_mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+
+  let len = left.len().min(right.len());
+  let vector_end = len & !15;
+  let mut offset = 0;
+
+  while offset < vector_end {
+    // SAFETY: the cfg above guarantees the target feature. `vector_end` is
+    // `len` rounded down to a multiple of 16, and `offset` advances by exactly
+    // 16, so both unaligned loads stay within the shorter input.
+    let equal = unsafe {
+      let left_chunk = _mm_loadu_si128(left.as_ptr().add(offset).cast());
+      let right_chunk = _mm_loadu_si128(right.as_ptr().add(offset).cast());
+      _mm_movemask_epi8(_mm_cmpeq_epi8(left_chunk, right_chunk)) as u32
+    };
+    // Bit `i` of the mask is set when byte `i` of both chunks is equal.
+    if equal != 0xFFFF {
+      return offset + (!equal).trailing_zeros() as usize;
+    }
+    offset += 16;
+  }
+
+  offset + common_prefix_len_scalar(&left[offset..len], &right[offset..len])
+}
+
+/// The 32-byte form of [`common_prefix_len_sse2`], which finishes the tail
+/// with 16-byte blocks.
+#[cfg(all(not(target_family = "windows"), target_arch = "x86_64", target_feature = "avx2"))]
+#[inline]
+fn common_prefix_len_avx2(left: &[u8], right: &[u8]) -> usize {
+  use std::arch::x86_64::{_mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_movemask_epi8};
+
+  let len = left.len().min(right.len());
+  let vector_end = len & !31;
+  let mut offset = 0;
+
+  while offset < vector_end {
+    // SAFETY: the cfg above guarantees the target feature. `vector_end` is
+    // `len` rounded down to a multiple of 32, and `offset` advances by exactly
+    // 32, so both unaligned loads stay within the shorter input.
+    let equal = unsafe {
+      let left_chunk = _mm256_loadu_si256(left.as_ptr().add(offset).cast());
+      let right_chunk = _mm256_loadu_si256(right.as_ptr().add(offset).cast());
+      _mm256_movemask_epi8(_mm256_cmpeq_epi8(left_chunk, right_chunk)) as u32
+    };
+    if equal != u32::MAX {
+      return offset + (!equal).trailing_zeros() as usize;
+    }
+    offset += 32;
+  }
+
+  offset + common_prefix_len_sse2(&left[offset..len], &right[offset..len])
+}
+
 /// Check if a path contains components or separators that need normalization.
-/// Uses `memchr` to jump between `/` positions — most bytes in a path aren't `/`,
-/// so this skips the vast majority of the input.
 #[inline]
 fn needs_relative_normalization(path: &str) -> bool {
   let bytes = path.as_bytes();
@@ -1730,33 +2195,13 @@ fn needs_relative_normalization(path: &str) -> bool {
       return true;
     }
   }
-  let mut offset = 0;
-  while let Some(pos) = memchr(b'/', &bytes[offset..]) {
-    let slash = offset + pos;
-    if slash + 1 < bytes.len() && bytes[slash + 1] == b'/' {
-      return true;
-    }
-    if slash + 1 < bytes.len() && bytes[slash + 1] == b'.' {
-      let after_dot = slash + 2;
-      // "/." at end or "/./"
-      if after_dot >= bytes.len() || bytes[after_dot] == b'/' {
-        return true;
-      }
-      // "/.." at end or "/../"
-      if bytes[after_dot] == b'.' && (after_dot + 1 >= bytes.len() || bytes[after_dot + 1] == b'/')
-      {
-        return true;
-      }
-    }
-    offset = slash + 1;
-  }
-  false
+  has_dirty_separator(bytes, b'/')
 }
 
 /// Fast path: no normalization needed. Operates directly on `&str` slices
 /// with zero intermediate allocation.
 #[cfg(not(all(target_os = "macos", target_arch = "aarch64", target_feature = "neon")))]
-fn relative_str_fast<'a>(target: &'a str, base: &str) -> Cow<'a, str> {
+fn relative_str_fast<'a>(target: &'a str, base: &str) -> RelativeStr<'a> {
   let common_byte_len = {
     #[cfg(target_family = "windows")]
     {
@@ -1804,40 +2249,7 @@ fn relative_str_fast<'a>(target: &'a str, base: &str) -> Cow<'a, str> {
     }
   }
 
-  let target_suffix = target[common_prefix..].trim_start_matches('/');
-  let ups = ups as usize;
-  if ups == 0 {
-    return Cow::Borrowed(target_suffix);
-  }
-  let suffix_iter = if target_suffix.is_empty() { None } else { Some(target_suffix) };
-  let mut result = String::with_capacity(ups * 3 + target_suffix.len());
-  std::iter::repeat_n("..", ups).chain(suffix_iter).for_each(|s| {
-    if !result.is_empty() {
-      result.push('/');
-    }
-    result.push_str(s);
-  });
-  Cow::Owned(result)
-}
-
-#[cfg(all(
-  any(target_os = "macos", target_os = "linux"),
-  any(test, all(target_os = "macos", target_arch = "aarch64", target_feature = "neon"))
-))]
-fn relative_str_from_parts<'a>(target: &'a str, common_prefix: usize, ups: usize) -> Cow<'a, str> {
-  let target_suffix = target[common_prefix..].trim_start_matches('/');
-  if ups == 0 {
-    return Cow::Borrowed(target_suffix);
-  }
-  let suffix_iter = if target_suffix.is_empty() { None } else { Some(target_suffix) };
-  let mut result = String::with_capacity(ups * 3 + target_suffix.len());
-  std::iter::repeat_n("..", ups).chain(suffix_iter).for_each(|s| {
-    if !result.is_empty() {
-      result.push('/');
-    }
-    result.push_str(s);
-  });
-  Cow::Owned(result)
+  RelativeStr::Upward { ups: ups as usize, suffix: target[common_prefix..].trim_start_matches('/') }
 }
 
 /// Slow path: normalize `.` and `..` components first, then compute relative path.
@@ -1899,10 +2311,10 @@ mod relative_str_tests {
   };
 
   fn assert_dispatch_and_suffix_validation_match_full_normalization(target: &str, base: &str) {
-    let dispatched = relative_str(target, base);
+    let dispatched = relative_str(target, base).into_cow();
     let target = target.trim_end_matches('/');
     let base = base.trim_end_matches('/');
-    let suffix_validated = relative_str_suffix_validated(target, base);
+    let suffix_validated = relative_str_suffix_validated(target, base).into_cow();
     let expected = relative_str_slow(target, base);
     assert_eq!(dispatched, expected, "production dispatch: target {target:?}, base {base:?}");
     assert_eq!(suffix_validated, expected, "suffix validation: target {target:?}, base {base:?}");
@@ -2057,6 +2469,75 @@ mod common_prefix_tests {
   }
 }
 
+#[cfg(all(test, not(target_family = "windows"), target_arch = "x86_64", target_feature = "sse2"))]
+mod x86_64_scan_tests {
+  use super::common_prefix_len_scalar;
+
+  fn bytes(len: usize) -> Vec<u8> {
+    (0..len).map(|index| ((index * 37 + 11) % 251) as u8).collect()
+  }
+
+  fn assert_common_prefix_matches_scalar(scan: fn(&[u8], &[u8]) -> usize) {
+    for len in 0..=256 {
+      let left = bytes(len);
+      assert_eq!(scan(&left, &left), len, "equal input length {len}");
+
+      for mismatch in 0..len {
+        let mut right = left.clone();
+        right[mismatch] ^= u8::MAX;
+        assert_eq!(
+          scan(&left, &right),
+          common_prefix_len_scalar(&left, &right),
+          "input length {len}, mismatch {mismatch}",
+        );
+      }
+    }
+
+    let input = bytes(256);
+    for left_len in 0..=256 {
+      for right_len in 0..=256 {
+        assert_eq!(
+          scan(&input[..left_len], &input[..right_len]),
+          common_prefix_len_scalar(&input[..left_len], &input[..right_len]),
+          "left length {left_len}, right length {right_len}",
+        );
+      }
+    }
+
+    for left_offset in 0..32 {
+      for right_offset in 0..32 {
+        for len in 0..=256 {
+          let mut left = [0xa5; 288];
+          let mut right = [0x5a; 288];
+          for index in 0..len {
+            let value = ((index * 37 + 11) % 251) as u8;
+            left[left_offset + index] = value;
+            right[right_offset + index] = value;
+          }
+          let left = &left[left_offset..left_offset + len];
+          let right = &right[right_offset..right_offset + len];
+          assert_eq!(
+            scan(left, right),
+            len,
+            "left offset {left_offset}, right offset {right_offset}, length {len}",
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn sse2_common_prefix_matches_scalar() {
+    assert_common_prefix_matches_scalar(super::common_prefix_len_sse2);
+  }
+
+  #[cfg(target_feature = "avx2")]
+  #[test]
+  fn avx2_common_prefix_matches_scalar() {
+    assert_common_prefix_matches_scalar(super::common_prefix_len_avx2);
+  }
+}
+
 fn replace_main_separator(input: &str) -> Option<String> {
   let sep = std::path::MAIN_SEPARATOR;
   let mut replaced: Option<String> = None;
@@ -0,0 +1,517 @@
+//! Lexical path algorithms over raw bytes.
+//!
+//! Nothing here depends on `std`: the `bytes` module uses these functions
+//! directly in `no_std` builds, and the `std` extension traits build their
+//! native `Path` fast paths on the same classifiers and component writer.
+
+use alloc::vec::Vec;
+use core::ops::DerefMut;
+
+use memchr::{memchr, memrchr};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrailingSeparator {
+  Preserve,
+  Strip,
+}
+
+pub(crate) fn windows_standalone_relative_bytes_are_representable(path: &[u8]) -> bool {
+  match path {
+    [b'/' | b'\\', ..] => false,
+    [drive, b':', ..] => !drive.is_ascii_alphabetic(),
+    _ => true,
+  }
+}
+
+/// Check whether `bytes` in a syntax with one separator and no prefixes needs
+/// normalization.
+#[inline]
+pub(crate) fn unix_bytes_need_normalization(
+  bytes: &[u8],
+  separator: u8,
+  trailing: TrailingSeparator,
+) -> bool {
+  if bytes.is_empty() {
+    return true;
+  }
+  if bytes == b"." || (trailing == TrailingSeparator::Preserve && bytes == [b'.', separator]) {
+    return false;
+  }
+  // A leading `.` needs normalization. A leading run of `..` can be clean when
+  // every remaining component is normal (`...` and `.foo` are normal names).
+  if bytes[0] == b'.' {
+    if bytes.len() == 1 || bytes[1] == separator {
+      return true;
+    }
+    if bytes[1] == b'.' && (bytes.len() == 2 || bytes[2] == separator) {
+      return !leading_parent_path_is_normalized(
+        bytes,
+        separator,
+        trailing == TrailingSeparator::Preserve,
+      );
+    }
+  }
+  // Trailing separator (unless the path is exactly the root separator).
+  if trailing == TrailingSeparator::Strip && bytes.len() > 1 && bytes[bytes.len() - 1] == separator
+  {
+    return true;
+  }
+  // Scan for duplicate separators and dot components.
+  has_dirty_separator(bytes, separator)
+}
+
+/// Check whether any separator in `bytes` starts an empty, `.`, or `..`
+/// component, using the widest vector scan compiled for the target.
+#[inline]
+pub(crate) fn has_dirty_separator(bytes: &[u8], separator: u8) -> bool {
+  #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+  {
+    has_dirty_separator_avx2(bytes, separator)
+  }
+  #[cfg(all(target_arch = "x86_64", target_feature = "sse2", not(target_feature = "avx2")))]
+  {
+    has_dirty_separator_sse2(bytes, separator)
+  }
+  #[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+  {
+    has_dirty_separator_scalar(bytes, separator)
+  }
+}
+
+/// Check whether the separator at `slash` starts an empty, `.`, or `..`
+/// component. A final separator does not.
+#[inline]
+fn separator_starts_dirty_component(bytes: &[u8], slash: usize, separator: u8) -> bool {
+  let next = slash + 1;
+  if next >= bytes.len() {
+    return false;
+  }
+  let b = bytes[next];
+  // `//` — consecutive slashes
+  if b == separator {
+    return true;
+  }
+  // `/.` — could be `/.` or `/..`
+  if b == b'.' {
+    let after_dot = next + 1;
+    // "/." at end or "/./"
+    if after_dot >= bytes.len() || bytes[after_dot] == separator {
+      return true;
+    }
+    // "/.." at end or "/../"
+    if bytes[after_dot] == b'.'
+      && (after_dot + 1 >= bytes.len() || bytes[after_dot + 1] == separator)
+    {
+      return true;
+    }
+  }
+  false
+}
+
+/// Uses `memchr` to jump between separator positions — most bytes in a path
+/// aren't separators, so this skips the vast majority of the input.
+#[inline]
+fn has_dirty_separator_scalar(bytes: &[u8], separator: u8) -> bool {
+  let mut offset = 0;
+  while let Some(pos) = memchr(separator, &bytes[offset..]) {
+    let slash = offset + pos;
+    if separator_starts_dirty_component(bytes, slash, separator) {
+      return true;
+    }
+    offset = slash + 1;
+  }
+  false
+}
+
+/// Compares each 16-byte block with the same block shifted by one byte, so one
+/// mask marks every separator followed by a separator or `.`. Only those
+/// candidates are checked for a whole dot component, which lets dotfiles such
+/// as `/.github` pass without a per-separator `memchr` call.
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+#[inline]
+fn has_dirty_separator_sse2(bytes: &[u8], separator: u8) -> bool {
+  use core::arch::x86_64::{
+    _mm_and_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_or_si128, _mm_set1_epi8,
+  };
+
+  let mut offset = 0;
+  while offset + 17 <= bytes.len() {
+    // SAFETY: the cfg above guarantees the target feature, and the loop
+    // condition keeps both 16-byte unaligned loads, at `offset` and
+    // `offset + 1`, within `bytes`.
+    let mut mask = unsafe {
+      let separators = _mm_set1_epi8(separator as i8);
+      let current = _mm_loadu_si128(bytes.as_ptr().add(offset).cast());
+      let next = _mm_loadu_si128(bytes.as_ptr().add(offset + 1).cast());
+      let followed_by_dirty_byte = _mm_or_si128(
+        _mm_cmpeq_epi8(next, separators),
+        _mm_cmpeq_epi8(next, _mm_set1_epi8(b'.' as i8)),
+      );
+      _mm_movemask_epi8(_mm_and_si128(_mm_cmpeq_epi8(current, separators), followed_by_dirty_byte))
+        as u32
+    };
+    while mask != 0 {
+      if separator_starts_dirty_component(bytes, offset + mask.trailing_zeros() as usize, separator)
+      {
+        return true;
+      }
+      mask &= mask - 1;
+    }
+    offset += 16;
+  }
+
+  // Every separator before `offset` has been checked, and the dirty check only
+  // reads forward from a separator, so the tail can be scanned on its own.
+  has_dirty_separator_scalar(&bytes[offset..], separator)
+}
+
+/// The 32-byte form of [`has_dirty_separator_sse2`], which finishes the tail
+/// with 16-byte blocks.
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+#[inline]
+fn has_dirty_separator_avx2(bytes: &[u8], separator: u8) -> bool {
+  use core::arch::x86_64::{
+    _mm256_and_si256, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_movemask_epi8, _mm256_or_si256,
+    _mm256_set1_epi8,
+  };
+
+  let mut offset = 0;
+  while offset + 33 <= bytes.len() {
+    // SAFETY: the cfg above guarantees the target feature, and the loop
+    // condition keeps both 32-byte unaligned loads, at `offset` and
+    // `offset + 1`, within `bytes`.
+    let mut mask = unsafe {
+      let separators = _mm256_set1_epi8(separator as i8);
+      let current = _mm256_loadu_si256(bytes.as_ptr().add(offset).cast());
+      let next = _mm256_loadu_si256(bytes.as_ptr().add(offset + 1).cast());
+      let followed_by_dirty_byte = _mm256_or_si256(
+        _mm256_cmpeq_epi8(next, separators),
+        _mm256_cmpeq_epi8(next, _mm256_set1_epi8(b'.' as i8)),
+      );
+      _mm256_movemask_epi8(_mm256_and_si256(
+        _mm256_cmpeq_epi8(current, separators),
+        followed_by_dirty_byte,
+      )) as u32
+    };
+    while mask != 0 {
+      if separator_starts_dirty_component(bytes, offset + mask.trailing_zeros() as usize, separator)
+      {
+        return true;
+      }
+      mask &= mask - 1;
+    }
+    offset += 32;
+  }
+
+  has_dirty_separator_sse2(&bytes[offset..], separator)
+}
+
+/// Check whether `bytes` in Windows syntax needs normalization.
+#[inline]
+pub(crate) fn windows_bytes_need_normalization(bytes: &[u8], trailing: TrailingSeparator) -> bool {
+  if bytes.is_empty() {
+    return true;
+  }
+  if bytes == b"." || (trailing == TrailingSeparator::Preserve && bytes == b".\\") {
+    return false;
+  }
+  // Any forward slash means normalization is needed (gets converted to `\`)
+  if memchr(b'/', bytes).is_some() {
+    return true;
+  }
+  // UNC prefix `\\` at start — always bail out to normalizer
+  if bytes.len() >= 2 && bytes[0] == b'\\' && bytes[1] == b'\\' {
+    return true;
+  }
+  // A bare drive `X:` normalizes to `X:.`. Other drive-relative paths keep
+  // their drive spelling and lexical form.
+  if bytes.len() == 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic() {
+    return true; // bare `C:`
+  }
+  // `C:.` and `C:.\` are canonical drive-relative current-directory
+  // spellings, but the same component before another segment is redundant.
+  if bytes.len() > 4
+    && bytes[1] == b':'
+    && bytes[0].is_ascii_alphabetic()
+    && bytes[2] == b'.'
+    && bytes[3] == b'\\'
+  {
+    return true;
+  }
+  // A drive-relative path can keep unresolved leading parents. Skip the
+  // generic `\..` rejection when the entire tail is already in canonical
+  // leading-parent form, such as `C:..\..\foo`.
+  if bytes.len() >= 4
+    && bytes[1] == b':'
+    && bytes[0].is_ascii_alphabetic()
+    && bytes[2] == b'.'
+    && bytes[3] == b'.'
+    && (bytes.len() == 4 || bytes[4] == b'\\')
+  {
+    return !leading_parent_path_is_normalized(
+      &bytes[2..],
+      b'\\',
+      trailing == TrailingSeparator::Preserve,
+    );
+  }
+  // A leading `.` needs normalization. A leading run of `..` can be clean when
+  // every remaining component is normal (`...` and `.foo` are normal names).
+  if bytes[0] == b'.' {
+    if bytes.len() == 1 || bytes[1] == b'\\' {
+      return true;
+    }
+    if bytes[1] == b'.' && (bytes.len() == 2 || bytes[2] == b'\\') {
+      return !leading_parent_path_is_normalized(
+        bytes,
+        b'\\',
+        trailing == TrailingSeparator::Preserve,
+      );
+    }
+  }
+  // Trailing `\` (unless path is `\` alone or `X:\`)
+  if trailing == TrailingSeparator::Strip && bytes[bytes.len() - 1] == b'\\' {
+    // `\` alone is clean
+    if bytes.len() == 1 {
+      return false;
+    }
+    // `X:\` is clean
+    if bytes.len() == 3 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic() {
+      return false;
+    }
+    return true;
+  }
+  // memchr scan for `\\` (consecutive), `\.`, `\..`
+  let mut offset = 0;
+  while let Some(pos) = memchr(b'\\', &bytes[offset..]) {
+    let slash = offset + pos;
+    let next = slash + 1;
+    if next < bytes.len() {
+      let b = bytes[next];
+      // `\\` — consecutive separators
+      if b == b'\\' {
+        return true;
+      }
+      // `\.` — could be `\.` or `\..`
+      if b == b'.' {
+        let after_dot = next + 1;
+        // "\." at end or "\.\"
+        if after_dot >= bytes.len() || bytes[after_dot] == b'\\' {
+          return true;
+        }
+        // "\.." at end or "\..\"
+        if bytes[after_dot] == b'.'
+          && (after_dot + 1 >= bytes.len() || bytes[after_dot + 1] == b'\\')
+        {
+          return true;
+        }
+      }
+    }
+    offset = next;
+  }
+  false
+}
+
+/// Return whether an unprefixed path starting with a `..` component is already
+/// in the exact spelling produced by `normalize_inner`.
+///
+/// A canonical path may contain one or more leading `..` components followed
+/// only by normal components. Empty components, `.`, a `..` after any normal
+/// component, and a trailing separator all require normalization.
+#[inline]
+fn leading_parent_path_is_normalized(bytes: &[u8], separator: u8, preserve_trailing: bool) -> bool {
+  debug_assert!(bytes == b".." || bytes.starts_with(&[b'.', b'.', separator]));
+
+  let mut offset = 0;
+  let mut saw_normal = false;
+  loop {
+    let end =
+      memchr(separator, &bytes[offset..]).map(|position| offset + position).unwrap_or(bytes.len());
+    let component = &bytes[offset..end];
+
+    if component == b".." {
+      if saw_normal {
+        return false;
+      }
+    } else if component.is_empty() || component == b"." {
+      return false;
+    } else {
+      saw_normal = true;
+    }
+
+    if end == bytes.len() {
+      return true;
+    }
+    offset = end + 1;
+    if offset == bytes.len() {
+      return preserve_trailing;
+    }
+  }
+}
+
+/// Growable byte storage that lexical normalization writes into.
+pub(crate) trait PathBytes: DerefMut<Target = [u8]> {
+  fn push(&mut self, byte: u8);
+
+  fn extend_from_slice(&mut self, bytes: &[u8]);
+
+  fn truncate(&mut self, len: usize);
+
+  /// Inserts `bytes` before the current contents.
+  #[cfg(target_family = "windows")]
+  fn insert_front(&mut self, bytes: &[u8]);
+}
+
+impl PathBytes for Vec<u8> {
+  fn push(&mut self, byte: u8) {
+    Vec::push(self, byte);
+  }
+
+  fn extend_from_slice(&mut self, bytes: &[u8]) {
+    Vec::extend_from_slice(self, bytes);
+  }
+
+  fn truncate(&mut self, len: usize) {
+    Vec::truncate(self, len);
+  }
+
+  #[cfg(target_family = "windows")]
+  fn insert_front(&mut self, bytes: &[u8]) {
+    self.splice(0..0, bytes.iter().copied());
+  }
+}
+
+/// A component after any prefix and root, as seen by the lexical normalizer.
+#[derive(Clone, Copy)]
+pub(crate) enum LexicalComponent<'a> {
+  CurDir,
+  ParentDir,
+  Normal(&'a [u8]),
+}
+
+/// Append `components` to `buf`, whose prefix and root end at `root_end`,
+/// resolving `..` against preceding normal components.
+///
+/// Returns the number of normal components left after the root.
+#[inline]
+pub(crate) fn push_lexical_components<'a>(
+  buf: &mut impl PathBytes,
+  root_end: usize,
+  has_root: bool,
+  separator: u8,
+  components: impl Iterator<Item = LexicalComponent<'a>>,
+) -> usize {
+  let mut depth: usize = 0; // count of Normal segments currently in buf
+  let mut need_sep = false;
+  for component in components {
+    match component {
+      LexicalComponent::CurDir => {}
+      LexicalComponent::ParentDir => {
+        if depth > 0 {
+          // Roll back the last Normal segment using memrchr.
+          let search_region = &buf[root_end..];
+          if let Some(pos) = memrchr(separator, search_region) {
+            buf.truncate(root_end + pos);
+          } else {
+            buf.truncate(root_end);
+          }
+          depth -= 1;
+          need_sep = buf.len() > root_end;
+        } else if !has_root {
+          // Relative path going above start: write ".." literally
+          if need_sep {
+            buf.push(separator);
+          }
+          buf.extend_from_slice(b"..");
+          need_sep = true;
+        }
+        // else: has_root && depth == 0 → ignore (can't go above root)
+      }
+      LexicalComponent::Normal(s) => {
+        if need_sep {
+          buf.push(separator);
+        }
+        buf.extend_from_slice(s);
+        depth += 1;
+        need_sep = true;
+      }
+    }
+  }
+  depth
+}
+
+#[cfg(all(test, target_arch = "x86_64", target_feature = "sse2"))]
+mod x86_64_scan_tests {
+  use super::{has_dirty_separator_scalar, has_dirty_separator_sse2};
+
+  /// Deterministic inputs over the bytes that decide a dirty separator, long
+  /// enough to cross several 32-byte blocks.
+  fn separator_inputs(separator: u8) -> impl Iterator<Item = Vec<u8>> {
+    let alphabet = [separator, b'.', b'a', 0xFF];
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..20_000).map(move |sample| {
+      let len = sample % 100;
+      (0..len)
+        .map(|_| {
+          state ^= state << 13;
+          state ^= state >> 7;
+          state ^= state << 17;
+          // Mostly normal bytes, so candidates are sparse like real paths.
+          if state.is_multiple_of(4) { alphabet[(state >> 8) as usize % 4] } else { b'a' }
+        })
+        .collect()
+    })
+  }
+
+  fn assert_dirty_separator_matches_scalar(scan: fn(&[u8], u8) -> bool) {
+    for separator in *b"/\\" {
+      for input in separator_inputs(separator) {
+        assert_eq!(
+          scan(&input, separator),
+          has_dirty_separator_scalar(&input, separator),
+          "{:?}",
+          input.escape_ascii().to_string(),
+        );
+      }
+
+      let sep = separator as char;
+      let patterns = [
+        format!("{sep}{sep}"),
+        format!("{sep}."),
+        format!("{sep}.{sep}"),
+        format!("{sep}.."),
+        format!("{sep}..{sep}"),
+        format!("{sep}.a"),
+        format!("{sep}..a"),
+        format!("{sep}..."),
+        format!("{sep}a"),
+      ];
+      for pattern in &patterns {
+        for position in 0..=96 {
+          for tail in 0..=40 {
+            let mut input = format!("{sep}a").repeat(position / 2).into_bytes();
+            input.resize(position, b'a');
+            input.extend_from_slice(pattern.as_bytes());
+            input.extend(std::iter::repeat_n(b'a', tail));
+            assert_eq!(
+              scan(&input, separator),
+              has_dirty_separator_scalar(&input, separator),
+              "{:?} at {position} with tail {tail}",
+              pattern,
+            );
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn sse2_dirty_separator_scan_matches_scalar() {
+    assert_dirty_separator_matches_scalar(has_dirty_separator_sse2);
+  }
+
+  #[cfg(target_feature = "avx2")]
+  #[test]
+  fn avx2_dirty_separator_scan_matches_scalar() {
+    assert_dirty_separator_matches_scalar(super::has_dirty_separator_avx2);
+  }
+}
@@ -0,0 +1,38 @@
+use std::{borrow::Cow, iter::FusedIterator, path::Path};
+
+use crate::impl_sugar_path::{ParentStep, normalized_parent};
+
+/// Iterator over the normalized lexical ancestors of a path.
+///
+/// Created by [`crate::SugarPath::lexical_ancestors`]. The first item is the
+/// normalized receiver, and each later item is the
+/// [`lexical_parent`](crate::SugarPath::lexical_parent) of the previous one.
+/// Iteration ends at a root or Windows prefix, or after the last normal
+/// component of a relative path has been removed, so it never invents
+/// additional `..` components.
+///
+/// Items borrow from the receiver whenever the receiver was already
+/// normalized; ancestors of a rebuilt path are owned.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct LexicalAncestors<'a> {
+  next: Option<Cow<'a, Path>>,
+}
+
+impl<'a> LexicalAncestors<'a> {
+  pub(crate) fn new(normalized: Cow<'a, Path>) -> Self {
+    Self { next: Some(normalized) }
+  }
+}
+
+impl<'a> Iterator for LexicalAncestors<'a> {
+  type Item = Cow<'a, Path>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let current = self.next.take()?;
+    self.next = normalized_parent(&current, ParentStep::Stop);
+    Some(current)
+  }
+}
+
+impl FusedIterator for LexicalAncestors<'_> {}
@@ -1,4 +1,7 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
+// The crate docs describe the `std` API, which a `no_std` build leaves out.
+#![cfg_attr(not(feature = "std"), allow(rustdoc::broken_intra_doc_links))]
 //! Host-native lexical path manipulation as extension methods on standard Rust types.
 //!
 //! SugarPath adds normalization, absolutization, relative paths, and slash
@@ -6,10 +9,14 @@
 //! accept the full native [`Path`](std::path::Path) domain, including non-UTF-8 paths; conversion
 //! to [`str`] or [`String`] makes the Unicode policy explicit.
 //!
-//! Import [`SugarPath`] for borrowed operations on [`Path`](std::path::Path) and `str`. Values
-//! such as [`PathBuf`](std::path::PathBuf) and [`String`] use the same methods through deref method
-//! lookup. Import [`SugarPathBuf`] for consuming operations that may reuse an
-//! owned path buffer.
+//! Import [`SugarPath`] for borrowed operations on [`Path`](std::path::Path),
+//! [`OsStr`](std::ffi::OsStr), and `str`. Values such as
+//! [`PathBuf`](std::path::PathBuf), [`OsString`](std::ffi::OsString), and
+//! [`String`] use the same methods through deref method lookup. Import
+//! [`SugarPathBuf`] for consuming operations that may reuse an owned path
+//! buffer, [`SugarString`] for consuming operations on paths kept as
+//! [`String`], and [`SugarCow`] for chaining operations on `Cow<Path>`
+//! results.
 //!
 //! # Quick start
 //!
@@ -38,10 +45,36 @@
 //! | Task | Borrowed or non-consuming | Consuming [`PathBuf`](std::path::PathBuf) |
 //! | --- | --- | --- |
 //! | Normalize | [`SugarPath::normalize`] | [`SugarPathBuf::into_normalized`] |
-//! | Make absolute | [`SugarPath::absolutize`], [`SugarPath::try_absolutize`], [`SugarPath::absolutize_with`] | — |
-//! | Make relative | [`SugarPath::relative`], [`SugarPath::try_relative`], [`SugarPath::relative_with`] | — |
+//! | Make absolute | [`SugarPath::absolutize`], [`SugarPath::try_absolutize`], [`SugarPath::absolutize_with`] | [`SugarPathBuf::into_absolutized`], [`SugarPathBuf::try_into_absolutized`], [`SugarPathBuf::into_absolutized_with`] |
+//! | Make relative | [`SugarPath::relative`], [`SugarPath::try_relative`], [`SugarPath::relative_with`] | [`SugarPathBuf::into_relative_with`] |
 //! | Convert separators | [`SugarPath::to_slash`], [`SugarPath::try_to_slash`], [`SugarPath::to_slash_lossy`] | [`SugarPathBuf::into_slash`], [`SugarPathBuf::try_into_slash`], [`SugarPathBuf::into_slash_lossy`] |
 //! | View text as a path | [`SugarPath::as_path`] | — |
+//! | Walk parents | [`SugarPath::lexical_parent`], [`SugarPath::lexical_ancestors`] | — |
+//! | Expand `~` and variables | [`SugarPath::expand_with`], [`SugarPath::expand`] | — |
+//! | Contract to `~` | [`SugarPath::contract_home`], [`SugarPath::contract_home_with`] | — |
+//! | Multi-dot extensions | [`SugarPath::split_extension_with`], [`SugarPath::full_extension`], [`SugarPath::with_full_extension`] | — |
+//!
+//! [`PathRemapper`] applies a table of longest-prefix substitutions, such as
+//! rewriting build-machine paths for reproducible output. [`PathTrie`] maps
+//! normalized paths to values and finds the deepest registered ancestor of a
+//! path, such as the package that owns a file. [`translate_path`] converts
+//! drive paths among Windows, WSL, MSYS2, and Cygwin spellings on any host.
+//! [`SugarPath::portability_issues`] reports components that would break on
+//! the [`PortabilityTargets`] you ship to, and [`sanitize_file_name`] turns
+//! arbitrary text into one file name that passes those checks. [`DisplayPath`]
+//! chooses the relative, `~`-contracted, or absolute spelling a diagnostic
+//! should print and can shorten it to a width budget with a middle ellipsis.
+//! [`rebase_glob`] re-expresses a glob pattern relative to another base
+//! directory, and [`glob::Pattern`] matches compiled glob patterns against
+//! lexically normalized paths. [`bytes::normalize`] normalizes raw path bytes
+//! in an explicit Unix or Windows [`bytes::Syntax`] on any host, and
+//! [`const_normalize!`] normalizes a Unix-syntax string constant at compile
+//! time.
+//!
+//! [`SugarPath::normalize_into`], [`SugarPath::relative_into`], and
+//! [`SugarPath::to_slash_into`] write into a caller's buffer instead of
+//! returning a new value, so one scratch [`PathBuf`](std::path::PathBuf) or
+//! [`String`] can be reused across many paths without reallocating.
 //!
 //! The ambient [`SugarPath::absolutize`] and [`SugarPath::relative`] methods
 //! panic only when required ambient path resolution fails. Their `try_*` forms
@@ -62,11 +95,14 @@
 //! must not be used as a security boundary. Use [`std::fs::canonicalize`] when
 //! physical filesystem identity is required.
 //!
-//! Parsing follows the compilation target's [`std::path`] rules. SugarPath
-//! does not parse Windows syntax on Unix or expose a caller-selected path
-//! syntax. [`SugarPath::normalize`] preserves one trailing separator on a
-//! non-root path. [`SugarPath::relative`] returns an empty path for equal inputs
-//! and removes a target's non-root trailing separator.
+//! The extension traits parse paths with the compilation target's
+//! [`std::path`] rules. To choose the syntax instead, use the [`bytes`]
+//! functions, which take an explicit [`bytes::Syntax`] and parse Windows
+//! prefixes and `\` separators the same way on every host.
+//! [`translate_path`] likewise parses Windows drive paths on any host.
+//! [`SugarPath::normalize`] preserves one trailing separator on a non-root
+//! path. [`SugarPath::relative`] returns an empty path for equal inputs and
+//! removes a target's non-root trailing separator.
 //!
 //! Normalization is exactly idempotent in the host-native encoded
 //! representation: normalizing a result again does not change its Unix or
@@ -93,10 +129,24 @@
 //!
 //! # Cargo features
 //!
+//! - `std` (default) provides the extension traits and every API over
+//!   `std::path`. Without it, the crate is `no_std` + `alloc` and provides
+//!   only the [`bytes`] module and [`const_normalize!`]. The features below enable `std`.
 //! - `cached_current_dir` caches the first successful ambient cwd lookup for
 //!   processes that treat cwd as stable. Later `std::env::set_current_dir`
 //!   calls are not observed. Explicit-cwd methods remain independent, and
 //!   Windows drive-relative paths still use authoritative per-drive cwd state.
+//! - `serde` adds the `serde` module, whose `#[serde(with = ...)]` adapters
+//!   write paths as `/`-separated strings and read them back as native
+//!   [`PathBuf`](std::path::PathBuf)s. Non-UTF-8 paths are serializer errors.
+//! - `camino` adds `SugarUtf8Path`, the lexical operations for camino's
+//!   `Utf8Path` with `Cow<Utf8Path>` results and infallible slash conversion.
+//! - `bumpalo` adds `SugarBump`, whose `normalize_in`, `relative_with_in`,
+//!   and `to_slash_in` store results in a `bumpalo::Bump` arena so paths
+//!   processed in bulk can be dropped together.
+//! - `rayon` adds the `rayon` module, whose `normalize_all`, `relative_all`,
+//!   and `to_slash_all` process a slice of paths in parallel in input order.
+//!   `relative_all` resolves its base once for the whole batch.
 //! - `codspeed` enables maintainer benchmark instrumentation and is not intended
 //!   for downstream applications.
 //!
@@ -104,9 +154,89 @@
 //! the [changelog](https://github.com/hyf0/sugar_path/blob/main/CHANGELOG.md)
 //! for release and migration information.
 
+extern crate alloc;
+
+pub mod bytes;
+mod const_normalize;
+#[cfg(feature = "std")]
+mod display_path;
+#[cfg(feature = "std")]
+mod expand;
+#[cfg(feature = "std")]
+mod extension;
+#[cfg(feature = "std")]
+pub mod glob;
+#[cfg(feature = "std")]
 mod impl_sugar_path;
+mod lexical;
+#[cfg(feature = "std")]
+mod lexical_ancestors;
+#[cfg(feature = "std")]
+mod path_remapper;
+#[cfg(feature = "std")]
+mod path_style;
+#[cfg(feature = "std")]
+mod path_trie;
+#[cfg(feature = "std")]
+mod portability;
+#[cfg(feature = "rayon")]
+pub mod rayon;
+#[cfg(feature = "std")]
+mod rebase_glob;
+#[cfg(feature = "std")]
+mod sanitize;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "bumpalo")]
+mod sugar_bump;
+#[cfg(feature = "std")]
+mod sugar_cow;
+#[cfg(feature = "std")]
 mod sugar_path;
+#[cfg(feature = "std")]
 mod sugar_path_buf;
+#[cfg(feature = "std")]
+mod sugar_string;
+#[cfg(feature = "camino")]
+mod sugar_utf8_path;
+#[cfg(feature = "std")]
 mod utils;
+#[cfg(target_family = "windows")]
+mod verbatim;
+#[cfg(feature = "std")]
+mod windows_names;
+#[cfg(feature = "std")]
+pub use display_path::{DisplayPath, DisplayPolicy};
+#[cfg(feature = "std")]
+pub use expand::{ExpandEnv, ExpandError};
+#[cfg(feature = "std")]
+pub use lexical_ancestors::LexicalAncestors;
+#[cfg(feature = "std")]
+pub use path_remapper::PathRemapper;
+#[cfg(feature = "std")]
+pub use path_style::{PathStyle, TranslateError, translate_path};
+#[cfg(feature = "std")]
+pub use path_trie::{PathTrie, PathTrieDescendants};
+#[cfg(feature = "std")]
+pub use portability::{PortabilityIssue, PortabilityIssueKind, PortabilityTargets};
+#[cfg(feature = "std")]
+pub use rebase_glob::{rebase_glob, rebase_glob_with};
+#[cfg(feature = "std")]
+pub use sanitize::{SanitizeOptions, sanitize_file_name};
+#[cfg(feature = "bumpalo")]
+pub use sugar_bump::SugarBump;
+#[cfg(feature = "std")]
+pub use sugar_cow::SugarCow;
+#[cfg(feature = "std")]
 pub use sugar_path::SugarPath;
+#[cfg(feature = "std")]
 pub use sugar_path_buf::SugarPathBuf;
+#[cfg(feature = "std")]
+pub use sugar_string::SugarString;
+#[cfg(feature = "camino")]
+pub use sugar_utf8_path::SugarUtf8Path;
+
+#[doc(hidden)]
+pub mod __private {
+  pub use crate::const_normalize::{normalize_unix, truncate};
+}
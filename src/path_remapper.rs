@@ -0,0 +1,194 @@
+use std::{
+  borrow::Cow,
+  path::{Component, Path, PathBuf},
+};
+
+use smallvec::SmallVec;
+
+#[cfg(target_family = "windows")]
+use crate::impl_sugar_path::push_windows_path_component;
+use crate::impl_sugar_path::{components_eq, normalize_for_resolution};
+
+type ComponentVec<'a> = SmallVec<[Component<'a>; 16]>;
+
+#[derive(Clone, Debug)]
+struct Mapping {
+  from: PathBuf,
+  from_len: usize,
+  to: PathBuf,
+}
+
+/// A table of path prefix substitutions, like rustc's `--remap-path-prefix`.
+///
+/// Each `from` prefix is lexically normalized once when it is inserted, with
+/// a non-root trailing separator removed. [`PathRemapper::remap`] normalizes
+/// its input the same way and replaces the longest matching prefix with its
+/// `to` replacement. Prefixes match whole components only: `/workspace/app`
+/// matches `/workspace/app/src` but never `/workspace/application`. When two
+/// mappings have equal normalized prefixes, the one inserted last wins.
+///
+/// Matching is lexical and host-native. It does not access the filesystem or
+/// resolve symlinks, and a relative prefix matches only relative inputs.
+/// A `from` that normalizes to `.` matches every relative input that does not
+/// start with `..`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use sugar_path::PathRemapper;
+///
+/// #[cfg(target_family = "unix")]
+/// {
+///   let remapper: PathRemapper =
+///     [("/home/ci/work", "/src"), ("/home/ci/work/vendor", "/vendor")].into_iter().collect();
+///   assert_eq!(remapper.remap("/home/ci/work/app/main.rs"), Path::new("/src/app/main.rs"));
+///   assert_eq!(remapper.remap("/home/ci/work/vendor/lib.rs"), Path::new("/vendor/lib.rs"));
+///   assert_eq!(remapper.remap("/home/ci/workspace"), Path::new("/home/ci/workspace"));
+/// }
+///
+/// #[cfg(target_family = "windows")]
+/// {
+///   let remapper: PathRemapper = [(r"C:\ci\work", r"\src")].into_iter().collect();
+///   assert_eq!(remapper.remap(r"c:\CI\Work\main.rs"), Path::new(r"\src\main.rs"));
+/// }
+/// ```
+///
+/// # Windows
+///
+/// Drive, UNC, and verbatim roots and path components compare with ASCII
+/// case ignored, the same rule used by [`crate::SugarPath::relative`].
+/// Different root kinds never match each other.
+#[derive(Clone, Debug, Default)]
+pub struct PathRemapper {
+  /// Sorted by descending prefix length so the first match is the longest.
+  mappings: Vec<Mapping>,
+}
+
+impl PathRemapper {
+  /// Creates an empty remapper.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a mapping from the `from` prefix to the `to` replacement.
+  ///
+  /// `from` is normalized immediately. `to` is used exactly as given, so the
+  /// caller controls the replacement spelling.
+  pub fn insert(&mut self, from: impl AsRef<Path>, to: impl Into<PathBuf>) {
+    let from = normalize_for_resolution(from.as_ref()).into_owned();
+    let from_len = significant_components(&from).count();
+    let index = self.mappings.partition_point(|mapping| mapping.from_len > from_len);
+    self.mappings.insert(index, Mapping { from, from_len, to: to.into() });
+  }
+
+  /// Returns the number of mappings.
+  pub fn len(&self) -> usize {
+    self.mappings.len()
+  }
+
+  /// Returns `true` if the remapper has no mappings.
+  pub fn is_empty(&self) -> bool {
+    self.mappings.is_empty()
+  }
+
+  /// Replaces the longest matching prefix of `path`.
+  ///
+  /// The remaining components of the normalized input are appended to the
+  /// replacement. If no prefix matches, the receiver is returned borrowed and
+  /// unchanged, without normalization.
+  pub fn remap<'a, P>(&self, path: &'a P) -> Cow<'a, Path>
+  where
+    P: AsRef<Path> + ?Sized,
+  {
+    let path = path.as_ref();
+    if self.mappings.is_empty() {
+      return Cow::Borrowed(path);
+    }
+
+    let normalized = normalize_for_resolution(path);
+    let components: ComponentVec<'_> = significant_components(&normalized).collect();
+    for mapping in &self.mappings {
+      if mapping.from_len > components.len()
+        || !prefix_matches(&mapping.from, mapping.from_len, &components)
+      {
+        continue;
+      }
+
+      return Cow::Owned(append_components(&mapping.to, &components[mapping.from_len..]));
+    }
+
+    Cow::Borrowed(path)
+  }
+}
+
+impl<F, T> FromIterator<(F, T)> for PathRemapper
+where
+  F: AsRef<Path>,
+  T: Into<PathBuf>,
+{
+  fn from_iter<I: IntoIterator<Item = (F, T)>>(iter: I) -> Self {
+    let mut remapper = Self::new();
+    remapper.extend(iter);
+    remapper
+  }
+}
+
+impl<F, T> Extend<(F, T)> for PathRemapper
+where
+  F: AsRef<Path>,
+  T: Into<PathBuf>,
+{
+  fn extend<I: IntoIterator<Item = (F, T)>>(&mut self, iter: I) {
+    for (from, to) in iter {
+      self.insert(from, to);
+    }
+  }
+}
+
+/// Replaces `from` with `to` when `from` is a component prefix of `path`.
+///
+/// Both `path` and `from` must already be normalized for resolution.
+pub(crate) fn replace_prefix(path: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+  let from_len = significant_components(from).count();
+  let components: ComponentVec<'_> = significant_components(path).collect();
+  (from_len <= components.len() && prefix_matches(from, from_len, &components))
+    .then(|| append_components(to, &components[from_len..]))
+}
+
+fn append_components(to: &Path, components: &[Component<'_>]) -> PathBuf {
+  // `PathBuf::push` would treat a normal component such as `C:foo` as a new
+  // drive prefix and discard the replacement, so append components textually.
+  #[cfg(target_family = "windows")]
+  {
+    let mut remapped = to.as_os_str().to_owned();
+    for component in components {
+      push_windows_path_component(&mut remapped, component.as_os_str(), true);
+    }
+    PathBuf::from(remapped)
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    let mut remapped = to.to_path_buf();
+    for component in components {
+      remapped.push(component);
+    }
+    remapped
+  }
+}
+
+/// A normalized path's components without the `.` that spells an empty
+/// relative path.
+fn significant_components(path: &Path) -> impl Iterator<Item = Component<'_>> {
+  path.components().filter(|component| !matches!(component, Component::CurDir))
+}
+
+fn prefix_matches(from: &Path, from_len: usize, components: &[Component<'_>]) -> bool {
+  if from_len == 0 {
+    // The relative root `.` contains relative paths that do not leave it.
+    return matches!(components.first(), None | Some(Component::Normal(_)));
+  }
+  significant_components(from)
+    .zip(components)
+    .all(|(from, component)| components_eq(&from, component))
+}
@@ -0,0 +1,217 @@
+use std::{borrow::Cow, error::Error, fmt};
+
+/// A spelling of Windows drive paths used by Windows and Unix-like shells.
+///
+/// Used with [`translate_path`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathStyle {
+  /// Native Windows paths such as `C:\Users\x` or `\\server\share\x`. Both `\`
+  /// and `/` are accepted as separators, and `\` is emitted.
+  Windows,
+  /// WSL drive mounts such as `/mnt/c/Users/x`.
+  Wsl,
+  /// MSYS2 and Git Bash drive paths such as `/c/Users/x`, with UNC paths
+  /// spelled `//server/share/x`.
+  Msys,
+  /// Cygwin drive paths such as `/cygdrive/c/Users/x`, with UNC paths spelled
+  /// `//server/share/x`.
+  Cygwin,
+}
+
+impl PathStyle {
+  /// The text before the drive letter of a drive path in a Unix-like style.
+  fn drive_mount(self) -> &'static str {
+    match self {
+      Self::Windows => "",
+      Self::Wsl => "/mnt/",
+      Self::Msys => "/",
+      Self::Cygwin => "/cygdrive/",
+    }
+  }
+
+  fn separator(self) -> char {
+    match self {
+      Self::Windows => '\\',
+      Self::Wsl | Self::Msys | Self::Cygwin => '/',
+    }
+  }
+
+  fn is_separator(self, byte: u8) -> bool {
+    match self {
+      Self::Windows => matches!(byte, b'\\' | b'/'),
+      Self::Wsl | Self::Msys | Self::Cygwin => byte == b'/',
+    }
+  }
+}
+
+/// The reason [`translate_path`] could not express a path in another style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TranslateError {
+  /// A UNC path has no spelling in the target style. WSL does not map UNC
+  /// shares.
+  Unc,
+  /// A Windows drive-relative path such as `C:x` or root-relative path such as
+  /// `\x` depends on per-drive state that a string transform cannot know.
+  DriveRelative,
+  /// A Windows verbatim (`\\?\`) or device (`\\.\`) path has no Unix-like
+  /// spelling.
+  Namespace,
+  /// A rooted Unix-like path is outside the style's drive mounts, such as
+  /// `/usr/bin`, so it has no Windows spelling.
+  NotMounted,
+}
+
+impl fmt::Display for TranslateError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Unc => "the UNC path has no mapping in the target path style",
+      Self::DriveRelative => {
+        "the drive-relative or root-relative Windows path has no fixed mapping"
+      }
+      Self::Namespace => "the verbatim or device Windows path has no mapping",
+      Self::NotMounted => "the rooted path is outside the drive mounts of its path style",
+    })
+  }
+}
+
+impl Error for TranslateError {}
+
+/// A parsed path: the root that differs between styles, then the remaining
+/// text after the root's separators.
+enum Root {
+  Drive(u8),
+  Unc,
+  Relative,
+}
+
+/// Translates `path` from one drive-path spelling to another.
+///
+/// This is a pure string transform that behaves the same on every host and
+/// does not consult WSL mount tables, MSYS2 `fstab`, or the filesystem. It
+/// rewrites the drive or UNC root and converts separators; other components,
+/// including `.` and `..`, are copied unchanged. A relative path only has
+/// its separators converted.
+///
+/// The drive letter keeps its spelling, the same way
+/// [`crate::SugarPath::normalize`] preserves it, so `/mnt/c/x` becomes `c:\x`
+/// and `C:\x` becomes `/mnt/C/x`. Drive mounts are recognized in either
+/// case. Lowercase the letter first when the target is a case-sensitive mount
+/// such as WSL's `/mnt/c`.
+///
+/// The input is returned borrowed when `from` and `to` are the same style, or
+/// when a relative path has no separator to convert.
+///
+/// # Errors
+///
+/// Returns [`TranslateError`] when the path has no spelling in `to`: a UNC
+/// path translated to [`PathStyle::Wsl`], a Windows drive-relative,
+/// root-relative, verbatim, or device path translated to a Unix-like style,
+/// or a rooted Unix-like path outside the drive mounts of `from`.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::{PathStyle, TranslateError, translate_path};
+///
+/// let windows = translate_path("/mnt/c/Users/x", PathStyle::Wsl, PathStyle::Windows).unwrap();
+/// assert_eq!(windows, r"c:\Users\x");
+///
+/// let msys = translate_path(r"C:\Users\x", PathStyle::Windows, PathStyle::Msys).unwrap();
+/// assert_eq!(msys, "/C/Users/x");
+///
+/// let cygwin = translate_path("/c/Users/x", PathStyle::Msys, PathStyle::Cygwin).unwrap();
+/// assert_eq!(cygwin, "/cygdrive/c/Users/x");
+///
+/// let unc = translate_path(r"\\server\share\x", PathStyle::Windows, PathStyle::Wsl);
+/// assert_eq!(unc, Err(TranslateError::Unc));
+/// ```
+pub fn translate_path(
+  path: &str,
+  from: PathStyle,
+  to: PathStyle,
+) -> Result<Cow<'_, str>, TranslateError> {
+  if from == to {
+    return Ok(Cow::Borrowed(path));
+  }
+
+  let (root, rest) = parse_root(path, from)?;
+  let mut translated = String::with_capacity(path.len() + to.drive_mount().len() + 2);
+  match root {
+    Root::Drive(drive) => {
+      translated.push_str(to.drive_mount());
+      translated.push(char::from(drive));
+      if to == PathStyle::Windows {
+        translated.push(':');
+        translated.push('\\');
+      } else if !rest.is_empty() {
+        translated.push('/');
+      }
+    }
+    Root::Unc => {
+      if to == PathStyle::Wsl {
+        return Err(TranslateError::Unc);
+      }
+      translated.push(to.separator());
+      translated.push(to.separator());
+    }
+    Root::Relative => {
+      if !rest.bytes().any(|byte| from.is_separator(byte) && char::from(byte) != to.separator()) {
+        return Ok(Cow::Borrowed(path));
+      }
+    }
+  }
+
+  translated.extend(
+    rest
+      .chars()
+      .map(|ch| if ch.is_ascii() && from.is_separator(ch as u8) { to.separator() } else { ch }),
+  );
+  Ok(Cow::Owned(translated))
+}
+
+fn parse_root(path: &str, style: PathStyle) -> Result<(Root, &str), TranslateError> {
+  let bytes = path.as_bytes();
+  let is_separator = |index: usize| bytes.get(index).is_some_and(|&byte| style.is_separator(byte));
+
+  if style == PathStyle::Windows {
+    if is_separator(0) && is_separator(1) {
+      if matches!(bytes.get(2), Some(b'?' | b'.')) && (bytes.len() == 3 || is_separator(3)) {
+        return Err(TranslateError::Namespace);
+      }
+      return Ok((Root::Unc, trim_separators(path, style)));
+    }
+    if is_separator(0) {
+      return Err(TranslateError::DriveRelative);
+    }
+    if let [drive, b':', ..] = bytes
+      && drive.is_ascii_alphabetic()
+    {
+      if !is_separator(2) {
+        return Err(TranslateError::DriveRelative);
+      }
+      return Ok((Root::Drive(*drive), trim_separators(&path[2..], style)));
+    }
+    return Ok((Root::Relative, path));
+  }
+
+  if !is_separator(0) {
+    return Ok((Root::Relative, path));
+  }
+  if style != PathStyle::Wsl && is_separator(1) && !is_separator(2) {
+    return Ok((Root::Unc, trim_separators(path, style)));
+  }
+  let mount = style.drive_mount();
+  if let Some(after_mount) = path.strip_prefix(mount)
+    && let [drive, tail @ ..] = after_mount.as_bytes()
+    && drive.is_ascii_alphabetic()
+    && tail.first().is_none_or(|&byte| style.is_separator(byte))
+  {
+    return Ok((Root::Drive(*drive), trim_separators(&after_mount[1..], style)));
+  }
+  Err(TranslateError::NotMounted)
+}
+
+fn trim_separators(path: &str, style: PathStyle) -> &str {
+  path.trim_start_matches(|ch: char| ch.is_ascii() && style.is_separator(ch as u8))
+}
@@ -0,0 +1,338 @@
+use std::{
+  cmp::Ordering,
+  iter::FusedIterator,
+  path::{Component, Path, PathBuf},
+};
+
+use smallvec::SmallVec;
+
+use crate::impl_sugar_path::normalize_for_resolution;
+
+/// One trie edge. A normalized path becomes an optional prefix, then either
+/// its root or the count of its unresolved leading `..` components, then its
+/// normal components. Relative paths that leave `.` by a different number of
+/// parents therefore never share a node.
+#[derive(Clone, Debug)]
+enum Key {
+  Prefix(Box<[u8]>),
+  Root,
+  Start(usize),
+  Normal(Box<[u8]>),
+}
+
+#[derive(Clone, Copy)]
+enum KeyRef<'a> {
+  Prefix(&'a [u8]),
+  Root,
+  Start(usize),
+  Normal(&'a [u8]),
+}
+
+impl Key {
+  fn from_ref(key: KeyRef<'_>) -> Self {
+    match key {
+      KeyRef::Prefix(bytes) => Self::Prefix(fold_case(bytes)),
+      KeyRef::Root => Self::Root,
+      KeyRef::Start(parents) => Self::Start(parents),
+      KeyRef::Normal(bytes) => Self::Normal(fold_case(bytes)),
+    }
+  }
+
+  fn cmp_ref(&self, probe: KeyRef<'_>) -> Ordering {
+    match (self, probe) {
+      (Self::Prefix(stored), KeyRef::Prefix(probe))
+      | (Self::Normal(stored), KeyRef::Normal(probe)) => cmp_folded(stored, probe),
+      (Self::Start(stored), KeyRef::Start(probe)) => stored.cmp(&probe),
+      (Self::Root, KeyRef::Root) => Ordering::Equal,
+      (stored, probe) => stored.rank().cmp(&probe.rank()),
+    }
+  }
+
+  fn rank(&self) -> u8 {
+    match self {
+      Self::Prefix(_) => 0,
+      Self::Root => 1,
+      Self::Start(_) => 2,
+      Self::Normal(_) => 3,
+    }
+  }
+}
+
+impl KeyRef<'_> {
+  fn rank(self) -> u8 {
+    match self {
+      Self::Prefix(_) => 0,
+      Self::Root => 1,
+      Self::Start(_) => 2,
+      Self::Normal(_) => 3,
+    }
+  }
+}
+
+/// Store keys in the host's comparison form: exact bytes on Unix and WASI,
+/// ASCII-lowercased encoded bytes on Windows.
+fn fold_case(bytes: &[u8]) -> Box<[u8]> {
+  #[cfg(target_family = "windows")]
+  {
+    bytes.to_ascii_lowercase().into_boxed_slice()
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    bytes.into()
+  }
+}
+
+fn cmp_folded(stored: &[u8], probe: &[u8]) -> Ordering {
+  #[cfg(target_family = "windows")]
+  {
+    stored.iter().copied().cmp(probe.iter().map(u8::to_ascii_lowercase))
+  }
+  #[cfg(not(target_family = "windows"))]
+  {
+    stored.cmp(probe)
+  }
+}
+
+/// Walk a normalized path as trie keys.
+fn for_each_key<'a>(path: &'a Path, mut visit: impl FnMut(KeyRef<'a>) -> bool) {
+  let mut parents = 0;
+  let mut started = false;
+  for component in path.components() {
+    let key = match component {
+      Component::Prefix(prefix) => KeyRef::Prefix(prefix.as_os_str().as_encoded_bytes()),
+      Component::RootDir => {
+        started = true;
+        KeyRef::Root
+      }
+      Component::CurDir => continue,
+      Component::ParentDir => {
+        parents += 1;
+        continue;
+      }
+      Component::Normal(normal) => {
+        if !started {
+          started = true;
+          if !visit(KeyRef::Start(parents)) {
+            return;
+          }
+        }
+        KeyRef::Normal(normal.as_encoded_bytes())
+      }
+    };
+    if !visit(key) {
+      return;
+    }
+  }
+  if !started {
+    visit(KeyRef::Start(parents));
+  }
+}
+
+#[derive(Clone, Debug)]
+struct Node<V> {
+  entry: Option<(PathBuf, V)>,
+  /// Sorted by [`Key::cmp_ref`] for binary search without allocating probes.
+  children: Vec<(Key, Node<V>)>,
+}
+
+impl<V> Node<V> {
+  const fn new() -> Self {
+    Self { entry: None, children: Vec::new() }
+  }
+
+  fn child(&self, key: KeyRef<'_>) -> Option<&Self> {
+    let index = self.children.binary_search_by(|(stored, _)| stored.cmp_ref(key)).ok()?;
+    Some(&self.children[index].1)
+  }
+
+  fn child_mut_or_insert(&mut self, key: KeyRef<'_>) -> &mut Self {
+    let index = match self.children.binary_search_by(|(stored, _)| stored.cmp_ref(key)) {
+      Ok(index) => index,
+      Err(index) => {
+        self.children.insert(index, (Key::from_ref(key), Self::new()));
+        index
+      }
+    };
+    &mut self.children[index].1
+  }
+}
+
+/// A map from lexically normalized paths to values with longest-prefix lookup.
+///
+/// Keys are normalized with [`crate::SugarPath::normalize`]'s component rules
+/// and compared component by component, so `/a/./b/` and `/a/b` are the same
+/// key and `/a/bc` is never below `/a/b`. [`PathTrie::longest_prefix`] finds
+/// the deepest registered ancestor of a path, such as the package that owns a
+/// file. Lookups do not access the filesystem or resolve symlinks.
+///
+/// Relative keys are kept apart from rooted keys, and relative keys are
+/// grouped by their count of unresolved leading `..` components: `.` contains
+/// `a/b`, but neither `.` nor `a` contains `../a`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use sugar_path::PathTrie;
+///
+/// let mut packages = PathTrie::new();
+/// packages.insert("packages/app", "app");
+/// packages.insert("packages/app/vendor/lib", "lib");
+///
+/// let (root, owner) = packages.longest_prefix("packages/app/src/../vendor/lib/index.js").unwrap();
+/// assert_eq!((root, *owner), (Path::new("packages/app/vendor/lib"), "lib"));
+/// assert_eq!(packages.longest_prefix("packages/application/index.js"), None);
+/// ```
+///
+/// # Windows
+///
+/// Prefixes and components compare with ASCII case ignored, so `C:\a` and
+/// `c:\A` address the same entry. Drive, UNC, verbatim, and device roots stay
+/// distinct, as do drive-relative and absolute paths on one drive.
+#[derive(Clone, Debug)]
+pub struct PathTrie<V> {
+  root: Node<V>,
+  len: usize,
+}
+
+impl<V> Default for PathTrie<V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V> PathTrie<V> {
+  /// Creates an empty trie.
+  pub const fn new() -> Self {
+    Self { root: Node::new(), len: 0 }
+  }
+
+  /// Returns the number of entries.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns `true` if the trie has no entries.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Inserts `value` at the normalized `path`, returning the previous value.
+  ///
+  /// When an equal key is already present, its value is replaced and the
+  /// originally inserted normalized spelling is kept.
+  pub fn insert(&mut self, path: impl AsRef<Path>, value: V) -> Option<V> {
+    let normalized = normalize_for_resolution(path.as_ref());
+    let mut keys: SmallVec<[KeyRef<'_>; 16]> = SmallVec::new();
+    for_each_key(&normalized, |key| {
+      keys.push(key);
+      true
+    });
+    let mut node = &mut self.root;
+    for key in keys {
+      node = node.child_mut_or_insert(key);
+    }
+    match &mut node.entry {
+      Some((_, existing)) => Some(std::mem::replace(existing, value)),
+      entry @ None => {
+        *entry = Some((normalized.into_owned(), value));
+        self.len += 1;
+        None
+      }
+    }
+  }
+
+  /// Returns the value stored at the normalized `path`.
+  pub fn get(&self, path: impl AsRef<Path>) -> Option<&V> {
+    self.node(path.as_ref())?.entry.as_ref().map(|(_, value)| value)
+  }
+
+  /// Returns the deepest entry whose key is `path` or one of its lexical
+  /// ancestors, with that entry's normalized key.
+  pub fn longest_prefix(&self, path: impl AsRef<Path>) -> Option<(&Path, &V)> {
+    let normalized = normalize_for_resolution(path.as_ref());
+    let mut node = &self.root;
+    let mut longest = node.entry.as_ref();
+    for_each_key(&normalized, |key| match node.child(key) {
+      Some(child) => {
+        node = child;
+        longest = node.entry.as_ref().or(longest);
+        true
+      }
+      None => false,
+    });
+    longest.map(|(path, value)| (path.as_path(), value))
+  }
+
+  /// Iterates over the entries at or below the normalized `prefix`.
+  ///
+  /// Entries are visited in depth-first order, parents before their
+  /// descendants. Siblings follow the host comparison order of their
+  /// components, not insertion order.
+  pub fn descendants(&self, prefix: impl AsRef<Path>) -> PathTrieDescendants<'_, V> {
+    PathTrieDescendants { stack: self.node(prefix.as_ref()).into_iter().collect() }
+  }
+
+  fn node(&self, path: &Path) -> Option<&Node<V>> {
+    let normalized = normalize_for_resolution(path);
+    let mut node = Some(&self.root);
+    for_each_key(&normalized, |key| {
+      node = node.and_then(|node| node.child(key));
+      node.is_some()
+    });
+    node
+  }
+}
+
+impl<P, V> FromIterator<(P, V)> for PathTrie<V>
+where
+  P: AsRef<Path>,
+{
+  fn from_iter<I: IntoIterator<Item = (P, V)>>(iter: I) -> Self {
+    let mut trie = Self::new();
+    trie.extend(iter);
+    trie
+  }
+}
+
+impl<P, V> Extend<(P, V)> for PathTrie<V>
+where
+  P: AsRef<Path>,
+{
+  fn extend<I: IntoIterator<Item = (P, V)>>(&mut self, iter: I) {
+    for (path, value) in iter {
+      self.insert(path, value);
+    }
+  }
+}
+
+/// Iterator over the entries at or below a prefix of a [`PathTrie`].
+///
+/// Created by [`PathTrie::descendants`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PathTrieDescendants<'a, V> {
+  stack: Vec<&'a Node<V>>,
+}
+
+impl<V> Clone for PathTrieDescendants<'_, V> {
+  fn clone(&self) -> Self {
+    Self { stack: self.stack.clone() }
+  }
+}
+
+impl<'a, V> Iterator for PathTrieDescendants<'a, V> {
+  type Item = (&'a Path, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some(node) = self.stack.pop() {
+      self.stack.extend(node.children.iter().rev().map(|(_, child)| child));
+      if let Some((path, value)) = &node.entry {
+        return Some((path.as_path(), value));
+      }
+    }
+    None
+  }
+}
+
+impl<V> FusedIterator for PathTrieDescendants<'_, V> {}
@@ -0,0 +1,211 @@
+use std::{
+  ffi::OsString,
+  fmt,
+  ops::{BitOr, BitOrAssign},
+  path::{Component, Path},
+};
+
+use crate::windows_names::{has_windows_trailing_dot_or_space, is_windows_reserved_name};
+
+/// A set of file systems that [`crate::SugarPath::portability_issues`] checks
+/// path components against.
+///
+/// Combine targets with `|`:
+///
+/// ```
+/// use sugar_path::PortabilityTargets;
+///
+/// let targets = PortabilityTargets::WINDOWS | PortabilityTargets::MACOS;
+/// assert!(targets.contains(PortabilityTargets::WINDOWS));
+/// assert!(!targets.contains(PortabilityTargets::POSIX));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PortabilityTargets {
+  bits: u8,
+}
+
+impl PortabilityTargets {
+  /// Win32 file names: no reserved device names, no `<>:"/\|?*` or control
+  /// characters, no trailing dot or space, valid Unicode, and at most 255
+  /// UTF-16 code units.
+  pub const WINDOWS: Self = Self { bits: 1 };
+  /// macOS file names: no `:`, valid Unicode, and at most 255 UTF-8 bytes.
+  pub const MACOS: Self = Self { bits: 1 << 1 };
+  /// The POSIX portable filename character set: ASCII letters, digits, `.`,
+  /// `_`, and `-`, with no leading `-`.
+  pub const POSIX: Self = Self { bits: 1 << 2 };
+  /// Every supported target.
+  pub const ALL: Self = Self { bits: Self::WINDOWS.bits | Self::MACOS.bits | Self::POSIX.bits };
+
+  /// Returns the empty set.
+  pub const fn empty() -> Self {
+    Self { bits: 0 }
+  }
+
+  /// Returns `true` if no target is selected.
+  pub const fn is_empty(self) -> bool {
+    self.bits == 0
+  }
+
+  /// Returns `true` if every target in `other` is also in `self`.
+  pub const fn contains(self, other: Self) -> bool {
+    self.bits & other.bits == other.bits
+  }
+
+  /// Returns the targets in either set.
+  pub const fn union(self, other: Self) -> Self {
+    Self { bits: self.bits | other.bits }
+  }
+
+  pub(crate) const fn intersection(self, other: Self) -> Self {
+    Self { bits: self.bits & other.bits }
+  }
+}
+
+impl BitOr for PortabilityTargets {
+  type Output = Self;
+
+  fn bitor(self, other: Self) -> Self {
+    self.union(other)
+  }
+}
+
+impl BitOrAssign for PortabilityTargets {
+  fn bitor_assign(&mut self, other: Self) {
+    *self = self.union(other);
+  }
+}
+
+impl fmt::Debug for PortabilityTargets {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let names = [(Self::WINDOWS, "WINDOWS"), (Self::MACOS, "MACOS"), (Self::POSIX, "POSIX")];
+    let mut set = f.debug_set();
+    for (target, name) in names {
+      if self.contains(target) {
+        set.entry(&format_args!("{name}"));
+      }
+    }
+    set.finish()
+  }
+}
+
+/// What makes a path component non-portable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PortabilityIssueKind {
+  /// The component is not valid Unicode.
+  NotUnicode,
+  /// The component names a reserved Windows device, such as `CON`, `NUL`, or
+  /// `COM1.txt`.
+  ReservedName,
+  /// The component contains a character the target does not allow. Each
+  /// distinct character is reported once per component.
+  IllegalCharacter(char),
+  /// The component starts with `-`, which the POSIX portable filename rules
+  /// exclude.
+  LeadingHyphen,
+  /// The component ends with a dot or space, which Win32 removes.
+  TrailingDotOrSpace,
+  /// The component exceeds the target's file-name length limit.
+  TooLong,
+}
+
+/// One problem with one component, reported by
+/// [`crate::SugarPath::portability_issues`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortabilityIssue {
+  /// The index of the offending component in [`Path::components`].
+  pub component_index: usize,
+  /// The offending component.
+  pub component: OsString,
+  /// The kind of problem.
+  pub kind: PortabilityIssueKind,
+  /// The checked targets that reject the component for this reason.
+  pub targets: PortabilityTargets,
+}
+
+const MAX_NAME_LEN: usize = 255;
+
+pub(crate) fn portability_issues(
+  path: &Path,
+  targets: PortabilityTargets,
+) -> Vec<PortabilityIssue> {
+  let mut issues = Vec::new();
+  if targets.is_empty() {
+    return issues;
+  }
+
+  for (component_index, component) in path.components().enumerate() {
+    let Component::Normal(component) = component else {
+      continue;
+    };
+    let first_issue = issues.len();
+    let mut report = |kind, affected: PortabilityTargets| {
+      let affected = affected.intersection(targets);
+      if affected.is_empty() {
+        return;
+      }
+      match issues[first_issue..].iter_mut().find(|issue| issue.kind == kind) {
+        Some(issue) => issue.targets |= affected,
+        None => issues.push(PortabilityIssue {
+          component_index,
+          component: component.to_owned(),
+          kind,
+          targets: affected,
+        }),
+      }
+    };
+
+    let bytes = component.as_encoded_bytes();
+    if bytes.utf8_chunks().any(|chunk| !chunk.invalid().is_empty()) {
+      report(PortabilityIssueKind::NotUnicode, PortabilityTargets::ALL);
+    }
+    if is_windows_reserved_name(bytes) {
+      report(PortabilityIssueKind::ReservedName, PortabilityTargets::WINDOWS);
+    }
+
+    let mut utf16_len = 0;
+    for chunk in bytes.utf8_chunks() {
+      // Invalid bytes have no exact UTF-16 length and are already reported as
+      // `NotUnicode`, so count one unit per byte.
+      utf16_len += chunk.invalid().len();
+      for ch in chunk.valid().chars() {
+        utf16_len += ch.len_utf16();
+        let affected = illegal_character_targets(ch);
+        if !affected.is_empty() {
+          report(PortabilityIssueKind::IllegalCharacter(ch), affected);
+        }
+      }
+    }
+
+    if bytes.first() == Some(&b'-') {
+      report(PortabilityIssueKind::LeadingHyphen, PortabilityTargets::POSIX);
+    }
+    if has_windows_trailing_dot_or_space(bytes) {
+      report(PortabilityIssueKind::TrailingDotOrSpace, PortabilityTargets::WINDOWS);
+    }
+    let mut too_long = PortabilityTargets::empty();
+    if utf16_len > MAX_NAME_LEN {
+      too_long |= PortabilityTargets::WINDOWS;
+    }
+    if bytes.len() > MAX_NAME_LEN {
+      too_long |= PortabilityTargets::MACOS;
+    }
+    report(PortabilityIssueKind::TooLong, too_long);
+  }
+  issues
+}
+
+pub(crate) fn illegal_character_targets(ch: char) -> PortabilityTargets {
+  let mut targets = PortabilityTargets::empty();
+  if matches!(ch, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' | '\0'..='\x1f') {
+    targets |= PortabilityTargets::WINDOWS;
+  }
+  if ch == ':' {
+    targets |= PortabilityTargets::MACOS;
+  }
+  if !matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9' | '.' | '_' | '-') {
+    targets |= PortabilityTargets::POSIX;
+  }
+  targets
+}
@@ -0,0 +1,93 @@
+//! Parallel batch operations over slices of paths.
+//!
+//! Each function runs the matching [`SugarPath`] method on every element
+//! through rayon's global thread pool and returns the results in input order.
+//! Every result equals what the method returns for that element on its own.
+//!
+//! [`relative_all`] reads the cwd at most once per batch, before the parallel
+//! loop, so one batch never mixes results resolved against different
+//! directories. [`normalize_all`] and [`to_slash_all`] never read it.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::Path;
+//! use sugar_path::rayon::{normalize_all, relative_all};
+//!
+//! let graph = ["workspace/src/./index.js", "workspace/src/util/../lib.js"];
+//! let normalized = normalize_all(&graph);
+//! assert_eq!(normalized, [Path::new("workspace/src/index.js"), Path::new("workspace/src/lib.js")]);
+//!
+//! let relative = relative_all(&normalized, "workspace");
+//! assert_eq!(relative, [Path::new("src/index.js"), Path::new("src/lib.js")]);
+//! ```
+
+use std::{borrow::Cow, path::Path};
+
+use ::rayon::prelude::*;
+
+use crate::{SugarPath, utils::try_get_current_dir};
+
+/// Normalizes every path in `paths` in parallel.
+///
+/// Element `i` of the result equals `paths[i].as_ref().normalize()`.
+pub fn normalize_all<P>(paths: &[P]) -> Vec<Cow<'_, Path>>
+where
+  P: AsRef<Path> + Sync,
+{
+  paths.par_iter().map(|path| path.as_ref().normalize()).collect()
+}
+
+/// Returns the path from `base` to every path in `targets`, in parallel.
+///
+/// Element `i` of the result equals `targets[i].as_ref().relative(&base)`.
+/// `base` is resolved once before the batch starts, so each target only
+/// resolves itself: an absolute base is normalized, and a relative base is
+/// absolutized against the current directory. A target that would borrow from
+/// a per-element call also borrows here, and a dirty base can let more
+/// results borrow.
+///
+/// When `base` or any target is relative, the current directory is read once
+/// before the parallel loop, and every element is computed with
+/// [`SugarPath::relative_with`] against that value. A batch of absolute paths
+/// does not read it. On Windows, a drive-relative `base` or target on a drive
+/// other than the cwd's is therefore resolved as
+/// [`SugarPath::absolutize_with`] and `relative_with` document, instead of
+/// against that drive's own cwd.
+///
+/// # Panics
+///
+/// Panics if the current directory is needed and cannot be read, even when
+/// every target would relate to `base` lexically.
+pub fn relative_all<P>(targets: &[P], base: impl AsRef<Path>) -> Vec<Cow<'_, Path>>
+where
+  P: AsRef<Path> + Sync,
+{
+  let base = base.as_ref();
+  if base.is_absolute() && targets.iter().all(|target| target.as_ref().is_absolute()) {
+    let base = base.normalize();
+    let base: &Path = &base;
+    return targets.par_iter().map(|target| target.as_ref().relative(base)).collect();
+  }
+
+  let cwd =
+    try_get_current_dir().expect("failed to resolve relative paths against the current directory");
+  let cwd: &Path = &cwd;
+  let base = base.absolutize_with(cwd);
+  let base: &Path = &base;
+  targets.par_iter().map(|target| target.as_ref().relative_with(base, cwd)).collect()
+}
+
+/// Converts every path in `paths` to a `/`-separated string, in parallel.
+///
+/// Element `i` of the result equals `paths[i].as_ref().to_slash()`.
+///
+/// # Panics
+///
+/// Panics if any path is not valid Unicode, as [`SugarPath::to_slash`] does.
+pub fn to_slash_all<P>(paths: &[P]) -> Vec<Cow<'_, str>>
+where
+  P: AsRef<Path> + Sync,
+{
+  paths.par_iter().map(|path| path.as_ref().to_slash()).collect()
+}
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use crate::{SugarPath, impl_sugar_path::path_buf_into_slash};
+
+/// Returns `true` if a `/`-separated glob segment must be matched as a
+/// pattern rather than as a literal name.
+pub(crate) fn is_magic_segment(segment: &str) -> bool {
+  segment.contains(['*', '?', '[', ']', '{', '}', '\\'])
+}
+
+/// Re-expresses a glob pattern written relative to `from_dir` as a pattern
+/// relative to `to_dir`.
+///
+/// The pattern is split on `/`. Its leading segments that contain no glob
+/// syntax (`*`, `?`, `[`, `]`, `{`, `}`, or the escape `\`) are treated as a
+/// path: a relative prefix is joined to `from_dir` and made relative to
+/// `to_dir` with [`SugarPath::relative`], and an absolute prefix is
+/// normalized and stays absolute. Everything from the first magic segment on
+/// is copied verbatim, so `**`, `{a,b}`, and `[..]` keep their meaning.
+///
+/// The result is `/`-separated. A leading `!` negation and a trailing `/` are
+/// kept, and a pattern that resolves to `to_dir` itself becomes `.`. Glob
+/// characters in directory names that come from `from_dir` or `to_dir` are
+/// escaped as one-character classes, such as `[[]`, `\` is escaped as `\\`,
+/// and a `!` that starts a name is escaped as `\!`, so they still match
+/// literally and never turn the pattern into a negation.
+///
+/// [`rebase_glob_with`] takes an explicit cwd instead of reading the process
+/// cwd.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::rebase_glob;
+///
+/// assert_eq!(rebase_glob("./src/**/*.ts", "packages/app", "."), "packages/app/src/**/*.ts");
+/// assert_eq!(rebase_glob("../shared/{a,b}/*.css", "packages/app", "packages"), "shared/{a,b}/*.css");
+/// assert_eq!(rebase_glob("!**/*.test.ts", "packages/app", "packages/lib"), "!../app/**/*.test.ts");
+/// #[cfg(target_family = "unix")]
+/// assert_eq!(rebase_glob("/opt/./cache/**", "packages/app", "."), "/opt/cache/**");
+/// ```
+///
+/// # Panics
+///
+/// Panics if the rebased literal prefix is not valid Unicode. Like
+/// [`SugarPath::relative`], it also panics when one of `from_dir` and
+/// `to_dir` is relative, the other is absolute, and the process cwd cannot be
+/// read.
+pub fn rebase_glob(pattern: &str, from_dir: impl AsRef<Path>, to_dir: impl AsRef<Path>) -> String {
+  rebase_glob_by(pattern, |literal_path| {
+    from_dir.as_ref().join(literal_path).relative(to_dir).into_owned()
+  })
+}
+
+/// Like [`rebase_glob`], but resolves a relative `from_dir` or `to_dir`
+/// against the explicit `cwd` instead of the process cwd.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::rebase_glob_with;
+///
+/// #[cfg(target_family = "unix")]
+/// assert_eq!(rebase_glob_with("src/**", "packages/app", "/work", "/work"), "packages/app/src/**");
+/// #[cfg(target_family = "windows")]
+/// assert_eq!(
+///   rebase_glob_with("src/**", r"packages\app", r"C:\work", r"C:\work"),
+///   "packages/app/src/**",
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics if the rebased literal prefix is not valid Unicode, or if the
+/// calculation needs `cwd` and `cwd` is not absolute.
+pub fn rebase_glob_with(
+  pattern: &str,
+  from_dir: impl AsRef<Path>,
+  to_dir: impl AsRef<Path>,
+  cwd: impl AsRef<Path> + Into<PathBuf>,
+) -> String {
+  rebase_glob_by(pattern, |literal_path| {
+    from_dir.as_ref().join(literal_path).relative_with(to_dir, cwd).into_owned()
+  })
+}
+
+/// Rebases `pattern`, resolving a relative literal prefix with `rebase`.
+fn rebase_glob_by(pattern: &str, rebase: impl FnOnce(&Path) -> PathBuf) -> String {
+  let (negation, body) = match pattern.strip_prefix('!') {
+    Some(body) => ("!", body),
+    None => ("", pattern),
+  };
+  let mut magic_start = body.len();
+  let mut offset = 0;
+  for segment in body.split('/') {
+    if is_magic_segment(segment) {
+      magic_start = offset;
+      break;
+    }
+    offset += segment.len() + 1;
+  }
+  let (literal, magic) = body.split_at(magic_start);
+
+  let literal_path = Path::new(literal);
+  let rebased = if literal_path.is_absolute() {
+    literal_path.normalize().into_owned()
+  } else {
+    rebase(literal_path)
+  };
+
+  let mut output = String::with_capacity(pattern.len() + rebased.as_os_str().len());
+  output.push_str(negation);
+  push_escaped(&mut output, &path_buf_into_slash(rebased));
+  if !magic.is_empty() {
+    if !output[negation.len()..].is_empty() && !output.ends_with('/') {
+      output.push('/');
+    }
+    output.push_str(magic);
+  } else if output.len() == negation.len() {
+    output.push('.');
+  }
+  if body.ends_with('/') && !output.ends_with('/') {
+    output.push('/');
+  }
+  output
+}
+
+fn push_escaped(output: &mut String, literal: &str) {
+  let mut name_start = true;
+  for ch in literal.chars() {
+    let at_name_start = std::mem::replace(&mut name_start, ch == '/');
+    match ch {
+      '*' | '?' | '[' | ']' | '{' | '}' => {
+        output.push('[');
+        output.push(ch);
+        output.push(']');
+      }
+      // `\` escapes the next character even inside a class, so `[\]` would
+      // not close; escape it with itself instead.
+      '\\' => output.push_str("\\\\"),
+      // A leading `!` would negate the pattern, and some matchers read `!(`
+      // as an extglob in any name.
+      '!' if at_name_start => output.push_str("\\!"),
+      _ => output.push(ch),
+    }
+  }
+}
@@ -0,0 +1,186 @@
+use std::borrow::Cow;
+
+use crate::{
+  PortabilityTargets,
+  portability::illegal_character_targets,
+  windows_names::{has_windows_trailing_dot_or_space, is_windows_reserved_name},
+};
+
+/// Options for [`sanitize_file_name`].
+///
+/// The default targets are [`PortabilityTargets::WINDOWS`] and
+/// [`PortabilityTargets::MACOS`], the replacement is `_`, and names are capped
+/// at 255 bytes.
+///
+/// ```
+/// use sugar_path::{PortabilityTargets, SanitizeOptions};
+///
+/// let options = SanitizeOptions::new().targets(PortabilityTargets::WINDOWS).replacement('-').max_len(64);
+/// # let _ = options;
+/// let options = SanitizeOptions::new().targets(PortabilityTargets::ALL).max_len(64);
+/// # let _ = options;
+/// ```
+#[derive(Clone, Debug)]
+pub struct SanitizeOptions {
+  targets: PortabilityTargets,
+  replacement: char,
+  max_len: usize,
+}
+
+impl Default for SanitizeOptions {
+  fn default() -> Self {
+    Self {
+      targets: PortabilityTargets::WINDOWS | PortabilityTargets::MACOS,
+      replacement: '_',
+      max_len: 255,
+    }
+  }
+}
+
+impl SanitizeOptions {
+  /// Creates the default options.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the file systems whose rules the name must satisfy.
+  ///
+  /// `/`, `\`, and NUL are replaced for every target set, because they
+  /// separate or terminate paths on some host.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the current replacement is not safe on `targets`, as described
+  /// for [`SanitizeOptions::replacement`].
+  pub fn targets(mut self, targets: PortabilityTargets) -> Self {
+    assert_safe_replacement(self.replacement, targets);
+    self.targets = targets;
+    self
+  }
+
+  /// Sets the character that replaces illegal characters, escapes reserved
+  /// names, and stands in for an empty name.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `replacement` is `/`, `\`, NUL, `.`, a space, or a character
+  /// that is illegal on one of the configured targets, since the result would
+  /// not be safe. With [`PortabilityTargets::POSIX`], `-` is rejected too,
+  /// because a replaced first character must not start the name with `-`, so
+  /// use an ASCII letter, digit, or `_`. Set the targets first when they allow
+  /// a replacement that the defaults reject.
+  pub fn replacement(mut self, replacement: char) -> Self {
+    assert_safe_replacement(replacement, self.targets);
+    self.replacement = replacement;
+    self
+  }
+
+  /// Sets the maximum length of the result in UTF-8 bytes.
+  ///
+  /// The limit is raised to the length of one replacement character when it
+  /// is smaller, so the result is never empty.
+  pub fn max_len(mut self, max_len: usize) -> Self {
+    self.max_len = max_len;
+    self
+  }
+}
+
+/// Turns arbitrary text into one file name that is safe on the configured
+/// targets.
+///
+/// The name is transformed in this order:
+///
+/// 1. `/`, `\`, NUL, and every character illegal on a target are replaced
+///    with the replacement character. With [`PortabilityTargets::POSIX`], a
+///    leading `-` is replaced too.
+/// 2. The name is cut to the byte limit on a UTF-8 character boundary.
+/// 3. For Windows, trailing dots and spaces are removed, and a reserved device
+///    name such as `CON` or `com1.txt` is escaped by prefixing the
+///    replacement character.
+/// 4. An empty result, `.`, or `..` becomes the replacement character.
+///
+/// The result borrows from `name` when only step 2 or step 3's trimming
+/// applies, including when `name` is already safe.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use sugar_path::{SanitizeOptions, sanitize_file_name};
+///
+/// let options = SanitizeOptions::new();
+/// assert_eq!(sanitize_file_name("chunk: a/b?", &options), "chunk_ a_b_");
+/// assert_eq!(sanitize_file_name("con.js", &options), "_con.js");
+/// assert_eq!(sanitize_file_name("notes. . ", &options), "notes");
+/// assert_eq!(sanitize_file_name("..", &options), "_");
+/// assert!(matches!(sanitize_file_name("index.js", &options), Cow::Borrowed("index.js")));
+/// ```
+pub fn sanitize_file_name<'a>(name: &'a str, options: &SanitizeOptions) -> Cow<'a, str> {
+  let targets = options.targets;
+  let replacement = options.replacement;
+  let windows = targets.contains(PortabilityTargets::WINDOWS);
+  let max_len = options.max_len.max(replacement.len_utf8());
+
+  let needs_replacement = |(index, ch): (usize, char)| {
+    matches!(ch, '/' | '\\' | '\0')
+      || !illegal_character_targets(ch).intersection(targets).is_empty()
+      || (index == 0 && ch == '-' && targets.contains(PortabilityTargets::POSIX))
+  };
+  let mut sanitized = if name.char_indices().any(needs_replacement) {
+    Cow::Owned(
+      name
+        .char_indices()
+        .map(|entry| if needs_replacement(entry) { replacement } else { entry.1 })
+        .collect(),
+    )
+  } else {
+    Cow::Borrowed(name)
+  };
+
+  truncate(&mut sanitized, max_len);
+  if windows {
+    trim_trailing_dots_and_spaces(&mut sanitized);
+    if is_windows_reserved_name(sanitized.as_bytes()) {
+      let mut escaped = String::with_capacity(sanitized.len() + replacement.len_utf8());
+      escaped.push(replacement);
+      escaped.push_str(&sanitized);
+      sanitized = Cow::Owned(escaped);
+      truncate(&mut sanitized, max_len);
+      trim_trailing_dots_and_spaces(&mut sanitized);
+    }
+  }
+
+  if matches!(&*sanitized, "" | "." | "..") {
+    return Cow::Owned(replacement.to_string());
+  }
+  sanitized
+}
+
+fn assert_safe_replacement(replacement: char, targets: PortabilityTargets) {
+  let leading_hyphen = replacement == '-' && targets.contains(PortabilityTargets::POSIX);
+  assert!(
+    !matches!(replacement, '/' | '\\' | '\0' | '.' | ' ')
+      && illegal_character_targets(replacement).intersection(targets).is_empty()
+      && !leading_hyphen,
+    "{replacement:?} cannot be used as a file-name replacement",
+  );
+}
+
+fn truncate(name: &mut Cow<'_, str>, max_len: usize) {
+  let len = name.floor_char_boundary(max_len);
+  match name {
+    Cow::Borrowed(borrowed) => *borrowed = &borrowed[..len],
+    Cow::Owned(owned) => owned.truncate(len),
+  }
+}
+
+fn trim_trailing_dots_and_spaces(name: &mut Cow<'_, str>) {
+  if !has_windows_trailing_dot_or_space(name.as_bytes()) {
+    return;
+  }
+  let len = name.trim_end_matches(['.', ' ']).len();
+  match name {
+    Cow::Borrowed(borrowed) => *borrowed = &borrowed[..len],
+    Cow::Owned(owned) => owned.truncate(len),
+  }
+}
@@ -0,0 +1,173 @@
+//! Serde adapters that store paths as `/`-separated strings.
+//!
+//! Use these modules with `#[serde(with = "...")]` on [`PathBuf`] fields.
+//! Each one serializes through [`SugarPath::try_to_slash`], so a path that is
+//! not valid Unicode produces a serializer error instead of a panic or a
+//! lossy string. Deserialization reads a string and returns a native
+//! [`PathBuf`], with `/` turned back into the native separator.
+//!
+//! | Module | Written form | Read form |
+//! | --- | --- | --- |
+//! | [`slash`] | the path with `/` separators | the native path |
+//! | [`normalized`] | the [normalized](SugarPath::normalize) path | the normalized native path |
+//! | [`relative_to_cwd`] | the path relative to the process cwd, or `.` | the path resolved against the process cwd |
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Manifest {
+//!   #[serde(with = "sugar_path::serde::normalized")]
+//!   entry: PathBuf,
+//! }
+//!
+//! let manifest = Manifest { entry: PathBuf::from("src").join(".").join("main.rs") };
+//! let json = serde_json::to_string(&manifest).unwrap();
+//! assert_eq!(json, r#"{"entry":"src/main.rs"}"#);
+//!
+//! let read: Manifest = serde_json::from_str(&json).unwrap();
+//! assert_eq!(read.entry, PathBuf::from("src").join("main.rs"));
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use ::serde::{Deserialize, Deserializer, Serializer, de, ser};
+
+use crate::{SugarPath, utils::try_get_current_dir};
+
+fn serialize_slash<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+  match path.try_to_slash() {
+    Some(slash) => serializer.serialize_str(&slash),
+    None => Err(ser::Error::custom(format_args!("path is not valid Unicode: {}", path.display()))),
+  }
+}
+
+fn deserialize_native<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+  let path = String::deserialize(deserializer)?;
+  #[cfg(target_family = "windows")]
+  let path = path.replace('/', "\\");
+  Ok(PathBuf::from(path))
+}
+
+fn serialize_relative_to_cwd<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+  let cwd = try_get_current_dir().map_err(ser::Error::custom)?;
+  let relative = path.relative_with(&*cwd, &*cwd);
+  if relative.as_os_str().is_empty() {
+    return serializer.serialize_str(".");
+  }
+  serialize_slash(&relative, serializer)
+}
+
+fn deserialize_absolutized<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> Result<PathBuf, D::Error> {
+  let path = deserialize_native(deserializer)?;
+  match path.try_absolutize() {
+    Ok(absolute) => Ok(absolute.into_owned()),
+    Err(error) => Err(de::Error::custom(error)),
+  }
+}
+
+/// Writes a path with `/` separators and reads it back as a native path.
+///
+/// Components are written unchanged; use [`normalized`] to also remove
+/// `.` and resolve `..`.
+pub mod slash {
+  use std::path::{Path, PathBuf};
+
+  use ::serde::{Deserializer, Serializer};
+
+  /// Serializes `path` with `/` separators.
+  ///
+  /// # Errors
+  ///
+  /// Returns a serializer error if `path` is not valid Unicode.
+  pub fn serialize<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    P: AsRef<Path> + ?Sized,
+    S: Serializer,
+  {
+    super::serialize_slash(path.as_ref(), serializer)
+  }
+
+  /// Deserializes a string as a native path.
+  ///
+  /// # Errors
+  ///
+  /// Returns the deserializer's error if the input is not a string.
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+    super::deserialize_native(deserializer)
+  }
+}
+
+/// Writes a normalized path with `/` separators and normalizes it again when
+/// reading.
+pub mod normalized {
+  use std::path::{Path, PathBuf};
+
+  use ::serde::{Deserializer, Serializer};
+
+  use crate::{SugarPath, SugarPathBuf};
+
+  /// Serializes [`SugarPath::normalize`] of `path` with `/` separators.
+  ///
+  /// # Errors
+  ///
+  /// Returns a serializer error if `path` is not valid Unicode.
+  pub fn serialize<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    P: AsRef<Path> + ?Sized,
+    S: Serializer,
+  {
+    super::serialize_slash(&path.as_ref().normalize(), serializer)
+  }
+
+  /// Deserializes a string as a normalized native path.
+  ///
+  /// # Errors
+  ///
+  /// Returns the deserializer's error if the input is not a string.
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+    super::deserialize_native(deserializer).map(SugarPathBuf::into_normalized)
+  }
+}
+
+/// Writes a path relative to the process cwd and resolves it against the
+/// process cwd when reading.
+///
+/// Relative paths keep a manifest valid when the whole tree moves. A path
+/// that has no relative spelling, such as one on another Windows drive, is
+/// written absolute. The path equal to the cwd is written as `.`.
+pub mod relative_to_cwd {
+  use std::path::{Path, PathBuf};
+
+  use ::serde::{Deserializer, Serializer};
+
+  /// Serializes `path` relative to the process cwd with `/` separators.
+  ///
+  /// # Errors
+  ///
+  /// Returns a serializer error if the process cwd cannot be read or the
+  /// relative path is not valid Unicode.
+  pub fn serialize<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    P: AsRef<Path> + ?Sized,
+    S: Serializer,
+  {
+    super::serialize_relative_to_cwd(path.as_ref(), serializer)
+  }
+
+  /// Deserializes a string as a native path resolved against the process
+  /// cwd.
+  ///
+  /// # Errors
+  ///
+  /// Returns a deserializer error if the input is not a string or the
+  /// process cwd cannot be read.
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+    super::deserialize_absolutized(deserializer)
+  }
+}
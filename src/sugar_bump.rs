@@ -0,0 +1,175 @@
+use std::{
+  ffi::OsStr,
+  path::{Path, PathBuf},
+};
+
+use bumpalo::{Bump, collections::Vec as BumpVec};
+
+use crate::{
+  impl_sugar_path::{
+    RelativeOutcome, relative_outcome_with, write_normalized_path, write_upward_relative,
+  },
+  lexical::PathBytes,
+};
+
+mod private {
+  use std::{ffi::OsStr, path::Path};
+
+  pub trait Sealed {}
+
+  impl Sealed for Path {}
+  impl Sealed for OsStr {}
+  impl Sealed for str {}
+}
+
+/// Path operations that write their results into a [`bumpalo::Bump`] arena.
+///
+/// Each method returns the same value as the [`SugarPath`] method it mirrors,
+/// copied into or built directly in `bump`, so results for many paths can be
+/// dropped together with the arena. Normalization, and relative results that
+/// borrow from the receiver or climb with `..` steps from canonical absolute
+/// inputs, are written into the arena without a heap allocation. Other
+/// relative results are computed like [`SugarPath::relative_with`] and then
+/// copied.
+///
+/// This trait is sealed and implemented for [`Path`], [`OsStr`], and `str`.
+///
+/// [`SugarPath`]: crate::SugarPath
+/// [`SugarPath::relative_with`]: crate::SugarPath::relative_with
+///
+/// # Examples
+///
+/// ```
+/// use bumpalo::Bump;
+/// use sugar_path::SugarBump;
+///
+/// let bump = Bump::new();
+/// let normalized = "src/./runtime/../lib.rs".normalize_in(&bump);
+/// assert_eq!(normalized.to_slash_in(&bump), "src/lib.rs");
+/// ```
+pub trait SugarBump: private::Sealed {
+  /// Arena form of [`SugarPath::normalize`](crate::SugarPath::normalize).
+  fn normalize_in<'bump>(&self, bump: &'bump Bump) -> &'bump Path;
+
+  /// Arena form of [`SugarPath::relative_with`](crate::SugarPath::relative_with).
+  ///
+  /// # Panics
+  ///
+  /// Panics if the calculation needs `cwd` and `cwd` is not absolute.
+  fn relative_with_in<'bump>(
+    &self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+    bump: &'bump Bump,
+  ) -> &'bump Path;
+
+  /// Arena form of [`SugarPath::to_slash`](crate::SugarPath::to_slash).
+  ///
+  /// # Panics
+  ///
+  /// Panics if this path is not valid UTF-8. A `str` receiver never panics.
+  fn to_slash_in<'bump>(&self, bump: &'bump Bump) -> &'bump str;
+}
+
+impl SugarBump for Path {
+  fn normalize_in<'bump>(&self, bump: &'bump Bump) -> &'bump Path {
+    // Normalization adds at most a `.` and a separator to its input.
+    let mut buf = BumpVec::with_capacity_in(self.as_os_str().len() + 2, bump);
+    write_normalized_path(&mut buf, self);
+    path_in(buf.into_bump_slice())
+  }
+
+  fn relative_with_in<'bump>(
+    &self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+    bump: &'bump Bump,
+  ) -> &'bump Path {
+    let bytes = match relative_outcome_with(self, base.as_ref(), cwd) {
+      RelativeOutcome::Upward { ups, suffix } => {
+        let mut buf = BumpVec::with_capacity_in(ups * 3 + suffix.len(), bump);
+        write_upward_relative(&mut buf, ups, suffix);
+        buf.into_bump_slice()
+      }
+      outcome => bump.alloc_slice_copy(outcome.into_cow_path().as_os_str().as_encoded_bytes()),
+    };
+    path_in(bytes)
+  }
+
+  fn to_slash_in<'bump>(&self, bump: &'bump Bump) -> &'bump str {
+    self.to_str().expect("path is not valid Unicode").to_slash_in(bump)
+  }
+}
+
+impl SugarBump for OsStr {
+  fn normalize_in<'bump>(&self, bump: &'bump Bump) -> &'bump Path {
+    Path::new(self).normalize_in(bump)
+  }
+
+  fn relative_with_in<'bump>(
+    &self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+    bump: &'bump Bump,
+  ) -> &'bump Path {
+    Path::new(self).relative_with_in(base, cwd, bump)
+  }
+
+  fn to_slash_in<'bump>(&self, bump: &'bump Bump) -> &'bump str {
+    Path::new(self).to_slash_in(bump)
+  }
+}
+
+impl SugarBump for str {
+  fn normalize_in<'bump>(&self, bump: &'bump Bump) -> &'bump Path {
+    Path::new(self).normalize_in(bump)
+  }
+
+  fn relative_with_in<'bump>(
+    &self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+    bump: &'bump Bump,
+  ) -> &'bump Path {
+    Path::new(self).relative_with_in(base, cwd, bump)
+  }
+
+  fn to_slash_in<'bump>(&self, bump: &'bump Bump) -> &'bump str {
+    let bytes = bump.alloc_slice_copy(self.as_bytes());
+    if std::path::MAIN_SEPARATOR != '/' {
+      for byte in bytes.iter_mut().filter(|byte| **byte == std::path::MAIN_SEPARATOR as u8) {
+        *byte = b'/';
+      }
+    }
+    // SAFETY: the bytes are a copy of `self` with one ASCII byte replaced by
+    // another, which keeps them UTF-8.
+    unsafe { std::str::from_utf8_unchecked(bytes) }
+  }
+}
+
+impl PathBytes for BumpVec<'_, u8> {
+  fn push(&mut self, byte: u8) {
+    BumpVec::push(self, byte);
+  }
+
+  fn extend_from_slice(&mut self, bytes: &[u8]) {
+    BumpVec::extend_from_slice(self, bytes);
+  }
+
+  fn truncate(&mut self, len: usize) {
+    BumpVec::truncate(self, len);
+  }
+
+  #[cfg(target_family = "windows")]
+  fn insert_front(&mut self, bytes: &[u8]) {
+    self.splice(0..0, bytes.iter().copied());
+  }
+}
+
+/// Views bytes written from the encoded bytes of paths, ASCII separators, and
+/// `.` as a path.
+fn path_in(bytes: &[u8]) -> &Path {
+  // SAFETY: every caller passes bytes built only from `OsStr` encoded bytes
+  // and ASCII, joined at boundaries that `OsStr` allows.
+  Path::new(unsafe { OsStr::from_encoded_bytes_unchecked(bytes) })
+}
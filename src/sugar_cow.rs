@@ -0,0 +1,97 @@
+use std::{
+  borrow::Cow,
+  path::{Path, PathBuf},
+};
+
+use crate::{SugarPath, SugarPathBuf};
+
+mod private {
+  use std::{borrow::Cow, path::Path};
+
+  pub trait Sealed {}
+
+  impl Sealed for Cow<'_, Path> {}
+}
+
+/// Chaining path operations on `Cow<Path>` results.
+///
+/// This trait is sealed and implemented only for `Cow<'_, Path>`, the result
+/// type of most [`SugarPath`] methods. Each method consumes the `Cow`: an
+/// owned path goes through the matching [`SugarPathBuf`] method and may reuse
+/// its storage, and a borrowed path goes through the matching [`SugarPath`]
+/// method and stays borrowed where that method borrows. Neither case calls
+/// [`Cow::into_owned`] first. Import the trait to use its methods.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use sugar_path::{SugarCow, SugarPath};
+///
+/// let cwd = std::env::current_dir().unwrap();
+/// let target = cwd.join("src").join(".").join("lib.rs");
+/// let slash = target.normalize().into_relative_with(&cwd, &cwd).into_slash();
+/// assert_eq!(slash, "src/lib.rs");
+/// ```
+pub trait SugarCow<'a>: private::Sealed {
+  /// Lexically normalizes this path like [`SugarPath::normalize`].
+  ///
+  /// An owned path is normalized with [`SugarPathBuf::into_normalized`]; a
+  /// borrowed path stays borrowed when it is already normalized.
+  #[must_use]
+  fn into_normalized(self) -> Cow<'a, Path>;
+
+  /// Computes the relative path from `base` to this path like
+  /// [`SugarPath::relative_with`].
+  ///
+  /// An owned path uses [`SugarPathBuf::into_relative_with`]; a borrowed path
+  /// may return a borrowed suffix of itself.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the calculation needs `cwd` and `cwd` is not absolute.
+  #[must_use]
+  fn into_relative_with(
+    self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'a, Path>;
+
+  /// Converts native separators to `/` like [`SugarPath::to_slash`].
+  ///
+  /// An owned path is converted in place with [`SugarPathBuf::into_slash`]; a
+  /// borrowed path stays borrowed when no separator needs replacing.
+  ///
+  /// # Panics
+  ///
+  /// Panics if this path is not valid UTF-8.
+  #[must_use]
+  fn into_slash(self) -> Cow<'a, str>;
+}
+
+impl<'a> SugarCow<'a> for Cow<'a, Path> {
+  fn into_normalized(self) -> Cow<'a, Path> {
+    match self {
+      Cow::Borrowed(path) => path.normalize(),
+      Cow::Owned(path) => Cow::Owned(path.into_normalized()),
+    }
+  }
+
+  fn into_relative_with(
+    self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'a, Path> {
+    match self {
+      Cow::Borrowed(path) => path.relative_with(base, cwd),
+      Cow::Owned(path) => Cow::Owned(path.into_relative_with(base, cwd)),
+    }
+  }
+
+  fn into_slash(self) -> Cow<'a, str> {
+    match self {
+      Cow::Borrowed(path) => path.to_slash(),
+      Cow::Owned(path) => Cow::Owned(path.into_slash()),
+    }
+  }
+}
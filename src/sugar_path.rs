@@ -1,24 +1,28 @@
 use std::{
   borrow::Cow,
+  ffi::OsStr,
   io,
   path::{Path, PathBuf},
 };
 
+use crate::{ExpandEnv, ExpandError, LexicalAncestors, PortabilityIssue, PortabilityTargets};
+
 mod private {
-  use std::path::Path;
+  use std::{ffi::OsStr, path::Path};
 
   pub trait Sealed {}
 
   impl Sealed for Path {}
+  impl Sealed for OsStr {}
   impl Sealed for str {}
 }
 
 /// Lexical path operations over borrowed standard Rust path and string types.
 ///
-/// Import this trait to call its methods on [`Path`] and `str`. [`PathBuf`],
-/// [`String`], and other types that dereference to one of those types use the
-/// methods through normal method lookup; they do not implement `SugarPath`
-/// themselves.
+/// Import this trait to call its methods on [`Path`], [`OsStr`], and `str`.
+/// [`PathBuf`], [`OsString`](std::ffi::OsString), [`String`], and other types
+/// that dereference to one of those types use the methods through normal
+/// method lookup; they do not implement `SugarPath` themselves.
 ///
 /// The trait is sealed because it is an extension-method namespace, not an
 /// abstraction for downstream path types. Generic APIs should accept a
@@ -99,6 +103,25 @@ pub trait SugarPath: private::Sealed {
   /// component as a prefix.
   fn normalize(&self) -> Cow<'_, Path>;
 
+  /// Writes the [`SugarPath::normalize`] result into `out`, replacing its
+  /// previous contents.
+  ///
+  /// The existing allocation of `out` is reused, so a scratch buffer that has
+  /// grown to fit the longest result normalizes further paths without
+  /// allocating.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::{Path, PathBuf};
+  /// use sugar_path::SugarPath;
+  ///
+  /// let mut out = PathBuf::new();
+  /// "src/./lib.rs".normalize_into(&mut out);
+  /// assert_eq!(out, Path::new("src/lib.rs").normalize());
+  /// ```
+  fn normalize_into(&self, out: &mut PathBuf);
+
   /// Resolves this path against the process current directory and normalizes it.
   ///
   /// Resolution removes a non-root trailing separator. An absolute input is
@@ -309,6 +332,31 @@ pub trait SugarPath: private::Sealed {
     cwd: impl AsRef<Path> + Into<PathBuf>,
   ) -> Cow<'_, Path>;
 
+  /// Writes the [`SugarPath::relative`] result into `out`, replacing its
+  /// previous contents.
+  ///
+  /// The existing allocation of `out` is reused. Results that are a slice of
+  /// the receiver, and `..` results of canonical absolute inputs, are written
+  /// without allocating. Other results, such as those of dirty inputs or
+  /// inputs resolved against the cwd, are computed in a temporary buffer
+  /// before the copy.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::{Path, PathBuf};
+  /// use sugar_path::SugarPath;
+  ///
+  /// let mut out = PathBuf::new();
+  /// Path::new("workspace/src").relative_into("workspace", &mut out);
+  /// assert_eq!(out, Path::new("src"));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics under the same conditions as [`SugarPath::relative`].
+  fn relative_into(&self, base: impl AsRef<Path>, out: &mut PathBuf);
+
   /// Converts native separators to `/`, requiring valid UTF-8.
   ///
   /// This operation does not normalize components. It returns a borrowed
@@ -369,10 +417,32 @@ pub trait SugarPath: private::Sealed {
   /// ```
   fn to_slash_lossy(&self) -> Cow<'_, str>;
 
+  /// Writes the [`SugarPath::to_slash`] result into `out`, replacing its
+  /// previous contents.
+  ///
+  /// The existing allocation of `out` is reused.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::PathBuf;
+  /// use sugar_path::SugarPath;
+  ///
+  /// let mut out = String::new();
+  /// PathBuf::from("src").join("lib.rs").to_slash_into(&mut out);
+  /// assert_eq!(out, "src/lib.rs");
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if this native path is not valid UTF-8. `out` is left empty in
+  /// that case.
+  fn to_slash_into(&self, out: &mut String);
+
   /// Views this value as a standard [`Path`] without allocating.
   ///
-  /// This is primarily useful for `str` and [`String`] receivers. It performs
-  /// no normalization, encoding conversion, or filesystem access.
+  /// This is primarily useful for `str`, [`String`], and [`OsStr`] receivers.
+  /// It performs no normalization, encoding conversion, or filesystem access.
   ///
   /// # Examples
   ///
@@ -383,4 +453,347 @@ pub trait SugarPath: private::Sealed {
   /// assert_eq!("src".as_path().join("lib.rs"), PathBuf::from("src").join("lib.rs"));
   /// ```
   fn as_path(&self) -> &Path;
+
+  /// Returns the lexical parent of this path after normalization.
+  ///
+  /// Unlike [`Path::parent`], which removes the last spelled component, this
+  /// resolves `.` and `..` first: `a/..` has parent `..`, and `..` has parent
+  /// `../..`. A relative path with one normal component has parent `.`.
+  /// Returns `None` for a root or a Windows prefix with no component to
+  /// remove, using the same root rules as [`SugarPath::normalize`]. The result
+  /// never keeps a non-root trailing separator.
+  ///
+  /// When the receiver is already normalized, a parent that is a prefix of the
+  /// receiver is borrowed. A parent that needs an added `..` or a rebuilt
+  /// spelling is owned.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarPath;
+  ///
+  /// assert_eq!(Path::new("a").join("..").lexical_parent().as_deref(), Some(Path::new("..")));
+  /// assert_eq!(Path::new("src").lexical_parent().as_deref(), Some(Path::new(".")));
+  ///
+  /// #[cfg(target_family = "unix")]
+  /// assert_eq!(Path::new("/").lexical_parent(), None);
+  ///
+  /// #[cfg(target_family = "windows")]
+  /// assert_eq!(Path::new(r"C:\").lexical_parent(), None);
+  /// ```
+  ///
+  /// # Windows
+  ///
+  /// A drive-relative path keeps its drive: `C:foo` has parent `C:.`, and
+  /// `C:.` has parent `C:..`. Drive, UNC, and verbatim roots have no parent.
+  fn lexical_parent(&self) -> Option<Cow<'_, Path>>;
+
+  /// Iterates over the normalized receiver and its successive lexical parents.
+  ///
+  /// The first item is [`SugarPath::normalize`] of the receiver. Iteration
+  /// stops at a root or Windows prefix, or once a relative path has no normal
+  /// components left, so `a/b` yields `a/b`, `a`, and `.`, and `../a` yields
+  /// `../a` and `..`. Items borrow from an already-normalized receiver.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::{Path, PathBuf};
+  /// use sugar_path::SugarPath;
+  ///
+  /// let path = PathBuf::from("src").join("..").join("dist").join("assets");
+  /// let ancestors: Vec<_> = path.lexical_ancestors().collect();
+  /// assert_eq!(ancestors, [Path::new("dist").join("assets"), PathBuf::from("dist"), PathBuf::from(".")]);
+  /// ```
+  fn lexical_ancestors(&self) -> LexicalAncestors<'_>;
+
+  /// Reports components that would be invalid or change meaning on `targets`.
+  ///
+  /// Each normal component from [`Path::components`] is checked as a file
+  /// name, so the same rules apply on every host: a Linux-built path such as
+  /// `dist/aux.js` or `assets/a:b.png` reports the component that breaks on
+  /// Windows. Components are not normalized first, and `.`, `..`, roots, and
+  /// Windows prefixes are not reported. Issues are ordered by component, and
+  /// one component can have several issues.
+  ///
+  /// The checks are lexical and cover only the rules listed on
+  /// [`PortabilityTargets`]. They do not detect names that collide only by
+  /// case or Unicode normalization.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use sugar_path::{PortabilityIssueKind, PortabilityTargets, SugarPath};
+  ///
+  /// let issues = "dist/con.js".portability_issues(PortabilityTargets::WINDOWS);
+  /// assert_eq!(issues.len(), 1);
+  /// assert_eq!(issues[0].component_index, 1);
+  /// assert_eq!(issues[0].kind, PortabilityIssueKind::ReservedName);
+  ///
+  /// assert!("dist/index.js".portability_issues(PortabilityTargets::ALL).is_empty());
+  /// ```
+  fn portability_issues(&self, targets: PortabilityTargets) -> Vec<PortabilityIssue>;
+
+  /// Expands a leading `~` and environment variables from `env`, then
+  /// normalizes the result with [`SugarPath::normalize`].
+  ///
+  /// A leading `~` alone or followed by a separator becomes the home directory
+  /// of `env`; `~user` is left unchanged. `$NAME` and `${NAME}` are expanded
+  /// anywhere in the path, where `NAME` is an ASCII letter or `_` followed by
+  /// ASCII letters, digits, or `_`. On Windows, `%NAME%` is expanded too, and a
+  /// `%` pair that cannot name a variable stays literal. Any other `$` is kept.
+  /// Expanded values are inserted as-is, so they may contain separators,
+  /// `..`, or non-UTF-8 data, and they are not expanded again.
+  ///
+  /// Expansion reads only `env`, never the process environment. When nothing
+  /// is expanded, the result is the normalized receiver and may borrow from it.
+  ///
+  /// # Errors
+  ///
+  /// Returns [`ExpandError::UnknownVariable`] for a variable missing from
+  /// `env`, [`ExpandError::MissingHome`] for `~` without a home directory, and
+  /// [`ExpandError::UnclosedBrace`] for `${` without `}`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::{ExpandEnv, ExpandError, SugarPath};
+  ///
+  /// #[cfg(target_family = "unix")]
+  /// {
+  ///   let env = ExpandEnv::new().home("/home/alice").var("XDG_CONFIG_HOME", "/home/alice/.config");
+  ///   assert_eq!("~/cache".expand_with(&env).unwrap(), Path::new("/home/alice/cache"));
+  ///   assert_eq!("${XDG_CONFIG_HOME}/y".expand_with(&env).unwrap(), Path::new("/home/alice/.config/y"));
+  ///   assert_eq!(
+  ///     "$TMPDIR/x".expand_with(&env),
+  ///     Err(ExpandError::UnknownVariable { name: "TMPDIR".to_owned() })
+  ///   );
+  /// }
+  ///
+  /// #[cfg(target_family = "windows")]
+  /// {
+  ///   let env = ExpandEnv::new().var("APPDATA", r"C:\Users\alice\AppData\Roaming");
+  ///   assert_eq!(r"%APPDATA%\z".expand_with(&env).unwrap(), Path::new(r"C:\Users\alice\AppData\Roaming\z"));
+  /// }
+  /// ```
+  fn expand_with(&self, env: &ExpandEnv) -> Result<Cow<'_, Path>, ExpandError>;
+
+  /// Expands a leading `~` and environment variables from the process
+  /// environment, then normalizes the result.
+  ///
+  /// This is [`SugarPath::expand_with`] using [`std::env::home_dir`] and
+  /// [`std::env::var_os`]. Prefer `expand_with` when the environment should be
+  /// explicit or testable.
+  ///
+  /// # Errors
+  ///
+  /// Returns the same errors as [`SugarPath::expand_with`].
+  fn expand(&self) -> Result<Cow<'_, Path>, ExpandError>;
+
+  /// Replaces a leading `home` directory with `~` for display.
+  ///
+  /// The receiver and `home` are normalized and compared component by
+  /// component, so `/home/alice` contracts `/home/alice/./src/` to `~/src`
+  /// but leaves `/home/alice2` alone. A receiver equal to `home` becomes `~`.
+  /// Remaining components are joined with the native separator, and a
+  /// non-root trailing separator is not kept. When the receiver is not inside
+  /// `home`, the result is [`SugarPath::normalize`] of the receiver.
+  ///
+  /// `home` must be absolute. An empty or relative `home`, such as an unset
+  /// `HOME` read as `""`, contracts nothing, so a relative receiver is never
+  /// contracted. Use [`SugarPath::contract_home_with`] to resolve relative
+  /// receivers against a cwd.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarPath;
+  ///
+  /// #[cfg(target_family = "unix")]
+  /// {
+  ///   assert_eq!("/home/alice/project/src/a.rs".contract_home("/home/alice"), Path::new("~/project/src/a.rs"));
+  ///   assert_eq!("/home/alice".contract_home("/home/alice/"), Path::new("~"));
+  ///   assert_eq!("/home/alice2/x".contract_home("/home/alice"), Path::new("/home/alice2/x"));
+  /// }
+  ///
+  /// #[cfg(target_family = "windows")]
+  /// assert_eq!(r"c:\users\Alice\x".contract_home(r"C:\Users\alice"), Path::new(r"~\x"));
+  /// ```
+  ///
+  /// # Windows
+  ///
+  /// Drive, UNC, and verbatim roots and components compare with ASCII case
+  /// ignored, the same rule used by [`SugarPath::relative`].
+  fn contract_home(&self, home: impl AsRef<Path>) -> Cow<'_, Path>;
+
+  /// Returns the shortest display spelling among the `~`-contracted path, the
+  /// path relative to `cwd`, and the absolute path.
+  ///
+  /// The receiver is resolved with [`SugarPath::absolutize_with`] before it
+  /// is matched against `home` as in [`SugarPath::contract_home`]. The
+  /// relative candidate is [`SugarPath::relative_with`] from `cwd`, spelled
+  /// `.` when the receiver is `cwd`. Lengths are compared in native encoded
+  /// bytes. On a tie the contracted spelling wins, then the relative one.
+  ///
+  /// This method never reads process cwd state. The result may borrow only
+  /// from the receiver.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarPath;
+  ///
+  /// #[cfg(target_family = "unix")]
+  /// {
+  ///   let home = "/home/alice";
+  ///   let cwd = "/home/alice/project";
+  ///   assert_eq!("src/a.rs".contract_home_with(home, cwd), Path::new("src/a.rs"));
+  ///   assert_eq!("/home/alice/notes.md".contract_home_with(home, cwd), Path::new("~/notes.md"));
+  ///   assert_eq!("/etc/hosts".contract_home_with(home, cwd), Path::new("/etc/hosts"));
+  /// }
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if the calculation needs `cwd` and `cwd` is not absolute.
+  fn contract_home_with(
+    &self,
+    home: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> Cow<'_, Path>;
+
+  /// Splits the file name into a stem and an extension, preferring the
+  /// longest matching multi-dot extension in `known`.
+  ///
+  /// Entries of `known` are written without the leading dot, such as `d.ts`
+  /// or `tar.gz`. When none of them ends the file name after a dot, the
+  /// extension is the text after the last dot, like [`Path::extension`]. A
+  /// dot at the start of the file name never starts an extension, so
+  /// `.eslintrc` has none and `.eslintrc.json` has `json`, the same as
+  /// Node's `path.extname`. Returns `None` when the path has no file name or
+  /// the file name has no extension.
+  ///
+  /// Matching works on native encoded bytes and is purely lexical, so names
+  /// that are not valid Unicode are split too.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::ffi::OsStr;
+  /// use sugar_path::SugarPath;
+  ///
+  /// let known = ["d.ts", "tar.gz"];
+  /// assert_eq!("src/foo.d.ts".split_extension_with(&known), Some((OsStr::new("foo"), OsStr::new("d.ts"))));
+  /// assert_eq!("foo.min.js".split_extension_with(&known), Some((OsStr::new("foo.min"), OsStr::new("js"))));
+  /// assert_eq!(".eslintrc".split_extension_with(&known), None);
+  /// ```
+  ///
+  /// # Windows
+  ///
+  /// Entries of `known` match with ASCII case ignored.
+  fn split_extension_with<S: AsRef<OsStr>>(&self, known: &[S]) -> Option<(&OsStr, &OsStr)>;
+
+  /// Returns everything after the first extension dot of the file name.
+  ///
+  /// `foo.d.ts` has the full extension `d.ts` and `archive.tar.gz` has
+  /// `tar.gz`. A dot at the start of the file name marks a dotfile rather
+  /// than an extension, so `.eslintrc.json` has `json` and `.eslintrc` has
+  /// none. A name ending in a dot, such as `foo.`, has an empty extension.
+  /// Works on native encoded bytes.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::ffi::OsStr;
+  /// use sugar_path::SugarPath;
+  ///
+  /// assert_eq!("types/foo.d.ts".full_extension(), Some(OsStr::new("d.ts")));
+  /// assert_eq!(".eslintrc.json".full_extension(), Some(OsStr::new("json")));
+  /// assert_eq!("Makefile".full_extension(), None);
+  /// ```
+  fn full_extension(&self) -> Option<&OsStr>;
+
+  /// Returns the path with its [full extension](SugarPath::full_extension)
+  /// replaced by `extension`.
+  ///
+  /// The extension is added when the file name has none and removed when
+  /// `extension` is empty. `extension` is written without the leading dot.
+  /// Like [`Path::with_extension`], a path without a file name is returned
+  /// unchanged, and text after the file name, such as a trailing separator,
+  /// is kept.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarPath;
+  ///
+  /// assert_eq!("src/foo.d.ts".with_full_extension("js"), Path::new("src/foo.js"));
+  /// assert_eq!("archive.tar.gz".with_full_extension(""), Path::new("archive"));
+  /// assert_eq!(".eslintrc".with_full_extension("json"), Path::new(".eslintrc.json"));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `extension` contains a path separator.
+  fn with_full_extension(&self, extension: impl AsRef<OsStr>) -> PathBuf;
+
+  /// Returns the normalized path in Windows verbatim (`\\?\`) form.
+  ///
+  /// Verbatim paths bypass Win32 path parsing and the `MAX_PATH` limit. After
+  /// [`SugarPath::normalize`], an absolute drive path such as `C:\x` becomes
+  /// `\\?\C:\x`, and a UNC path such as `\\srv\sh\x` becomes
+  /// `\\?\UNC\srv\sh\x`. Other normalized paths are returned unchanged:
+  /// relative, drive-relative, root-relative, device, and already verbatim
+  /// paths have no equivalent verbatim spelling that can be built lexically.
+  ///
+  /// A component that Win32 parsing would change also keeps the path
+  /// unchanged. This covers reserved device names such as `NUL` or `COM1.txt`
+  /// and names with a trailing dot or space, which refer to a different file
+  /// once the verbatim prefix disables that parsing.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarPath;
+  ///
+  /// assert_eq!(r"C:\x\..\y".to_verbatim(), Path::new(r"\\?\C:\y"));
+  /// assert_eq!(r"\\srv\sh\x".to_verbatim(), Path::new(r"\\?\UNC\srv\sh\x"));
+  /// assert_eq!(r"C:\logs\nul".to_verbatim(), Path::new(r"C:\logs\nul"));
+  /// ```
+  #[cfg(target_family = "windows")]
+  fn to_verbatim(&self) -> Cow<'_, Path>;
+
+  /// Removes a Windows verbatim (`\\?\`) prefix for display or for APIs that
+  /// do not accept verbatim paths.
+  ///
+  /// `\\?\C:\x` becomes `C:\x` and `\\?\UNC\srv\sh\x` becomes
+  /// `\\srv\sh\x`. The receiver is returned borrowed and unchanged when it is
+  /// not a verbatim drive or UNC path, or when removing the prefix would change
+  /// which file it names: a component containing a literal `/`, a `.` or `..`
+  /// component, a component with a trailing dot or space, a reserved device
+  /// name such as `CON` or `LPT1.log`, or a prefix without a root such as
+  /// `\\?\C:`. No other normalization is applied.
+  ///
+  /// A stripped drive path borrows from the receiver. A stripped UNC path is
+  /// owned.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarPath;
+  ///
+  /// assert_eq!(r"\\?\C:\x".strip_verbatim(), Path::new(r"C:\x"));
+  /// assert_eq!(r"\\?\UNC\srv\sh\x".strip_verbatim(), Path::new(r"\\srv\sh\x"));
+  /// assert_eq!(r"\\?\C:\a/b".strip_verbatim(), Path::new(r"\\?\C:\a/b"));
+  /// assert_eq!(r"\\?\C:\aux.txt".strip_verbatim(), Path::new(r"\\?\C:\aux.txt"));
+  /// ```
+  #[cfg(target_family = "windows")]
+  fn strip_verbatim(&self) -> Cow<'_, Path>;
 }
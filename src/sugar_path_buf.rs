@@ -1,7 +1,12 @@
-use std::path::PathBuf;
+use std::{
+  io,
+  path::{Path, PathBuf},
+};
 
 use crate::impl_sugar_path::{
-  normalize_owned_path_buf, path_buf_into_slash, path_buf_into_slash_lossy, try_path_buf_into_slash,
+  absolutize_owned_path_buf_with, normalize_owned_path_buf, path_buf_into_slash,
+  path_buf_into_slash_lossy, relative_owned_path_buf_with, try_absolutize_owned_path_buf,
+  try_path_buf_into_slash,
 };
 
 mod private {
@@ -40,6 +45,91 @@ pub trait SugarPathBuf: private::Sealed {
   #[must_use]
   fn into_normalized(self) -> PathBuf;
 
+  /// Resolves this path against the process cwd while reusing its allocation
+  /// when possible.
+  ///
+  /// The result matches [`crate::SugarPath::absolutize`]. A relative receiver
+  /// whose storage can hold the joined path has the cwd written in front of
+  /// its own bytes; an absolute receiver is normalized in place.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::PathBuf;
+  /// use sugar_path::SugarPathBuf;
+  ///
+  /// let cwd = std::env::current_dir().unwrap();
+  /// assert_eq!(PathBuf::from("src").into_absolutized(), cwd.join("src"));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if the process cwd is needed and cannot be read. Use
+  /// [`SugarPathBuf::try_into_absolutized`] to handle the error.
+  #[must_use]
+  fn into_absolutized(self) -> PathBuf;
+
+  /// Fallible form of [`SugarPathBuf::into_absolutized`].
+  ///
+  /// # Errors
+  ///
+  /// Returns the error from reading the process cwd, which is read only for
+  /// relative receivers.
+  fn try_into_absolutized(self) -> io::Result<PathBuf>;
+
+  /// Resolves this path against an explicit absolute `cwd` while reusing its
+  /// allocation when possible.
+  ///
+  /// The result matches [`crate::SugarPath::absolutize_with`], including its
+  /// Windows drive-relative rules.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::{Path, PathBuf};
+  /// use sugar_path::SugarPathBuf;
+  ///
+  /// let cwd = std::env::current_dir().unwrap();
+  /// let input = Path::new("src").join("..").join("lib.rs");
+  /// assert_eq!(input.into_absolutized_with(&cwd), cwd.join("lib.rs"));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if the receiver needs `cwd` and `cwd` is not absolute.
+  #[must_use]
+  fn into_absolutized_with(self, cwd: impl AsRef<Path> + Into<PathBuf>) -> PathBuf;
+
+  /// Computes the relative path from `base` to this path while reusing its
+  /// allocation when possible.
+  ///
+  /// The result matches [`crate::SugarPath::relative_with`]. When that method
+  /// would borrow part of the receiver, such as the clean descendant suffix
+  /// below `base`, the suffix is moved to the front of the receiver's storage
+  /// instead of being copied.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::{Path, PathBuf};
+  /// use sugar_path::SugarPathBuf;
+  ///
+  /// let cwd = std::env::current_dir().unwrap();
+  /// let target = cwd.join("workspace").join("src").join("lib.rs");
+  /// let relative = target.into_relative_with(cwd.join("workspace"), &cwd);
+  /// assert_eq!(relative, Path::new("src").join("lib.rs"));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if the calculation needs `cwd` and `cwd` is not absolute.
+  #[must_use]
+  fn into_relative_with(
+    self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> PathBuf;
+
   /// Converts native separators to `/`, requiring valid UTF-8.
   ///
   /// This has the same strict, non-normalizing conversion semantics as
@@ -120,6 +210,26 @@ impl SugarPathBuf for PathBuf {
     normalize_owned_path_buf(self)
   }
 
+  fn into_absolutized(self) -> PathBuf {
+    self.try_into_absolutized().expect("failed to resolve path against the current directory")
+  }
+
+  fn try_into_absolutized(self) -> io::Result<PathBuf> {
+    try_absolutize_owned_path_buf(self)
+  }
+
+  fn into_absolutized_with(self, cwd: impl AsRef<Path> + Into<PathBuf>) -> PathBuf {
+    absolutize_owned_path_buf_with(self, cwd)
+  }
+
+  fn into_relative_with(
+    self,
+    base: impl AsRef<Path>,
+    cwd: impl AsRef<Path> + Into<PathBuf>,
+  ) -> PathBuf {
+    relative_owned_path_buf_with(self, base.as_ref(), cwd)
+  }
+
   fn into_slash(self) -> String {
     path_buf_into_slash(self)
   }
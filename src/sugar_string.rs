@@ -0,0 +1,106 @@
+use std::path::Path;
+
+use crate::impl_sugar_path::{
+  normalize_owned_string, relative_owned_string, replace_main_separator_in_owned,
+};
+
+mod private {
+  pub trait Sealed {}
+
+  impl Sealed for String {}
+}
+
+/// Consuming path operations on paths kept as [`String`] text.
+///
+/// This trait is sealed and implemented only for [`String`]. Each method
+/// treats the text as a native path, like the [`crate::SugarPath`] method it
+/// mirrors, and returns a `String` instead of a [`PathBuf`](std::path::PathBuf)
+/// without another UTF-8 validation pass. The methods consume the receiver and
+/// may reuse its storage; reuse is an optimization, not part of the returned
+/// value's semantic contract. Import the trait to use its methods.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_path::SugarString;
+///
+/// let id = String::from("src/./runtime/../lib.rs");
+/// assert_eq!(id.into_normalized_string().into_slash(), "src/lib.rs");
+/// ```
+pub trait SugarString: private::Sealed {
+  /// Lexically normalizes this path text while reusing its allocation when
+  /// possible.
+  ///
+  /// The result is the text of [`crate::SugarPath::normalize`], with native
+  /// separators. Already-normalized text is returned unchanged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarString;
+  ///
+  /// let input = ["workspace", "src", "..", "dist"].join(std::path::MAIN_SEPARATOR_STR);
+  /// let normalized = input.into_normalized_string();
+  /// assert_eq!(Path::new(&normalized), Path::new("workspace").join("dist"));
+  /// ```
+  #[must_use]
+  fn into_normalized_string(self) -> String;
+
+  /// Converts native separators to `/` in place.
+  ///
+  /// Like [`crate::SugarPathBuf::into_slash`], this does not normalize. It
+  /// cannot fail because the receiver is already valid UTF-8, and it never
+  /// allocates.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use sugar_path::SugarString;
+  ///
+  /// let native = ["src", "lib.rs"].join(std::path::MAIN_SEPARATOR_STR);
+  /// assert_eq!(native.into_slash(), "src/lib.rs");
+  /// ```
+  #[must_use]
+  fn into_slash(self) -> String;
+
+  /// Computes the relative path from `base` to this path text while reusing
+  /// its allocation when possible.
+  ///
+  /// The result is the text of [`crate::SugarPath::relative`], with native
+  /// separators. When that method would borrow part of the receiver, such as
+  /// the clean descendant suffix below `base`, the suffix is moved to the front
+  /// of the receiver's storage instead of being copied.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::path::Path;
+  /// use sugar_path::SugarString;
+  ///
+  /// let cwd = std::env::current_dir().unwrap();
+  /// let module_id = cwd.join("src").join("lib.rs").into_os_string().into_string().unwrap();
+  /// assert_eq!(Path::new(&module_id.into_relative_string(&cwd)), Path::new("src").join("lib.rs"));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if required cwd state cannot be read, or if the result is not
+  /// valid Unicode because it contains part of a non-UTF-8 `base` or cwd.
+  #[must_use]
+  fn into_relative_string(self, base: impl AsRef<Path>) -> String;
+}
+
+impl SugarString for String {
+  fn into_normalized_string(self) -> String {
+    normalize_owned_string(self)
+  }
+
+  fn into_slash(self) -> String {
+    replace_main_separator_in_owned(self)
+  }
+
+  fn into_relative_string(self, base: impl AsRef<Path>) -> String {
+    relative_owned_string(self, base.as_ref())
+  }
+}
@@ -0,0 +1,175 @@
+use std::{
+  borrow::Cow,
+  io,
+  path::{Path, PathBuf},
+};
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::SugarPath;
+
+mod private {
+  use camino::Utf8Path;
+
+  pub trait Sealed {}
+
+  impl Sealed for Utf8Path {}
+}
+
+/// Lexical path operations on camino's [`Utf8Path`] that keep the UTF-8
+/// guarantee in their results.
+///
+/// Each method behaves like the [`SugarPath`] method with the same name,
+/// including its borrowing, panic, and Windows rules, and runs the `str`
+/// implementation on the receiver's text. Because the receiver and any
+/// explicit `base` or `cwd` are UTF-8, lexical results are UTF-8 too and are
+/// returned as [`Utf8Path`] without another validation pass. Only methods that
+/// read the process cwd check their result, since that directory may not be
+/// valid Unicode.
+///
+/// This trait is sealed and implemented only for [`Utf8Path`];
+/// [`Utf8PathBuf`] uses these methods through deref method lookup.
+///
+/// # Examples
+///
+/// ```
+/// use camino::Utf8Path;
+/// use sugar_path::SugarUtf8Path;
+///
+/// let normalized = Utf8Path::new("src/./runtime/../lib.rs").normalize();
+/// assert_eq!(normalized.to_slash(), "src/lib.rs");
+/// ```
+pub trait SugarUtf8Path: private::Sealed {
+  /// UTF-8 form of [`SugarPath::normalize`].
+  fn normalize(&self) -> Cow<'_, Utf8Path>;
+
+  /// UTF-8 form of [`SugarPath::absolutize`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the process cwd is needed and cannot be read or is not valid
+  /// Unicode. Use [`SugarUtf8Path::try_absolutize`] to handle the error.
+  fn absolutize(&self) -> Cow<'_, Utf8Path>;
+
+  /// UTF-8 form of [`SugarPath::try_absolutize`].
+  ///
+  /// # Errors
+  ///
+  /// Returns the error from reading the process cwd, or an
+  /// [`io::ErrorKind::InvalidData`] error if the result is not valid Unicode.
+  fn try_absolutize(&self) -> io::Result<Cow<'_, Utf8Path>>;
+
+  /// UTF-8 form of [`SugarPath::absolutize_with`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the receiver needs `cwd` and `cwd` is not absolute.
+  fn absolutize_with(&self, cwd: impl AsRef<Utf8Path>) -> Cow<'_, Utf8Path>;
+
+  /// UTF-8 form of [`SugarPath::relative`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if required cwd state cannot be read or the result is not valid
+  /// Unicode. Use [`SugarUtf8Path::try_relative`] to handle the error.
+  fn relative(&self, base: impl AsRef<Utf8Path>) -> Cow<'_, Utf8Path>;
+
+  /// UTF-8 form of [`SugarPath::try_relative`].
+  ///
+  /// # Errors
+  ///
+  /// Returns the error from reading required cwd state, or an
+  /// [`io::ErrorKind::InvalidData`] error if the result is not valid Unicode.
+  fn try_relative(&self, base: impl AsRef<Utf8Path>) -> io::Result<Cow<'_, Utf8Path>>;
+
+  /// UTF-8 form of [`SugarPath::relative_with`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the calculation needs `cwd` and `cwd` is not absolute.
+  fn relative_with(
+    &self,
+    base: impl AsRef<Utf8Path>,
+    cwd: impl AsRef<Utf8Path>,
+  ) -> Cow<'_, Utf8Path>;
+
+  /// Converts native separators to `/`.
+  ///
+  /// Unlike [`SugarPath::to_slash`], this cannot fail or panic. The result
+  /// borrows the receiver when no separator needs replacing.
+  fn to_slash(&self) -> Cow<'_, str>;
+}
+
+impl SugarUtf8Path for Utf8Path {
+  fn normalize(&self) -> Cow<'_, Utf8Path> {
+    from_utf8_parts(self.as_str().normalize())
+  }
+
+  fn absolutize(&self) -> Cow<'_, Utf8Path> {
+    self.try_absolutize().expect("failed to absolutize the path as UTF-8")
+  }
+
+  fn try_absolutize(&self) -> io::Result<Cow<'_, Utf8Path>> {
+    checked(self.as_str().try_absolutize()?)
+  }
+
+  fn absolutize_with(&self, cwd: impl AsRef<Utf8Path>) -> Cow<'_, Utf8Path> {
+    from_utf8_parts(self.as_str().absolutize_with(cwd.as_ref().as_std_path()))
+  }
+
+  fn relative(&self, base: impl AsRef<Utf8Path>) -> Cow<'_, Utf8Path> {
+    self.try_relative(base).expect("failed to compute the relative path as UTF-8")
+  }
+
+  fn try_relative(&self, base: impl AsRef<Utf8Path>) -> io::Result<Cow<'_, Utf8Path>> {
+    checked(self.as_str().try_relative(base.as_ref().as_std_path())?)
+  }
+
+  fn relative_with(
+    &self,
+    base: impl AsRef<Utf8Path>,
+    cwd: impl AsRef<Utf8Path>,
+  ) -> Cow<'_, Utf8Path> {
+    let base = base.as_ref().as_std_path();
+    from_utf8_parts(self.as_str().relative_with(base, cwd.as_ref().as_std_path()))
+  }
+
+  fn to_slash(&self) -> Cow<'_, str> {
+    self.as_str().to_slash()
+  }
+}
+
+/// Converts a lexical result built only from UTF-8 inputs.
+///
+/// Lexical operations copy whole components of their inputs and add ASCII
+/// separators, `.`, and `..`, so the result is UTF-8 when every input is.
+fn from_utf8_parts(path: Cow<'_, Path>) -> Cow<'_, Utf8Path> {
+  debug_assert!(path.to_str().is_some(), "a lexical result of UTF-8 inputs is UTF-8");
+  match path {
+    // SAFETY: see above; a borrowed result is part of the UTF-8 receiver or
+    // the static `.`.
+    Cow::Borrowed(path) => Cow::Borrowed(Utf8Path::new(unsafe {
+      std::str::from_utf8_unchecked(path.as_os_str().as_encoded_bytes())
+    })),
+    // SAFETY: see above.
+    Cow::Owned(path) => Cow::Owned(Utf8PathBuf::from(unsafe {
+      String::from_utf8_unchecked(path.into_os_string().into_encoded_bytes())
+    })),
+  }
+}
+
+/// Converts a result that may contain the process cwd.
+fn checked(path: Cow<'_, Path>) -> io::Result<Cow<'_, Utf8Path>> {
+  match path {
+    // A borrowed result is part of the UTF-8 receiver or the static `.`.
+    Cow::Borrowed(_) => Ok(from_utf8_parts(path)),
+    Cow::Owned(path) => {
+      Utf8PathBuf::from_path_buf(path).map(Cow::Owned).map_err(|path: PathBuf| {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("path is not valid Unicode: {}", path.display()),
+        )
+      })
+    }
+  }
+}
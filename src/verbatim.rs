@@ -0,0 +1,99 @@
+use std::{
+  borrow::Cow,
+  ffi::{OsStr, OsString},
+  path::{Component, Path, PathBuf, Prefix},
+};
+
+use crate::{
+  SugarPath,
+  windows_names::{has_windows_trailing_dot_or_space, is_windows_reserved_name},
+};
+
+/// Returns `true` if a component means the same file with and without a
+/// verbatim prefix. Win32 parsing treats `/` as a separator, strips trailing
+/// dots and spaces, and maps device names; verbatim parsing does none of that.
+fn component_is_verbatim_stable(component: &OsStr) -> bool {
+  let bytes = component.as_encoded_bytes();
+  !bytes.contains(&b'/')
+    && !has_windows_trailing_dot_or_space(bytes)
+    && !is_windows_reserved_name(bytes)
+}
+
+/// Checks the components after a prefix, which must start with a root.
+fn components_are_verbatim_stable<'a>(components: impl Iterator<Item = Component<'a>>) -> bool {
+  let mut components = components.peekable();
+  matches!(components.peek(), Some(Component::RootDir))
+    && components.all(|component| match component {
+      Component::RootDir => true,
+      Component::Normal(normal) => component_is_verbatim_stable(normal),
+      Component::Prefix(_) | Component::CurDir | Component::ParentDir => false,
+    })
+}
+
+pub(crate) fn to_verbatim(path: &Path) -> Cow<'_, Path> {
+  let normalized = path.normalize();
+  let mut components = normalized.components();
+  let Some(Component::Prefix(prefix)) = components.next() else {
+    return normalized;
+  };
+  if !matches!(prefix.kind(), Prefix::Disk(_) | Prefix::UNC(_, _))
+    || !components_are_verbatim_stable(components)
+  {
+    return normalized;
+  }
+
+  let bytes = normalized.as_os_str().as_encoded_bytes();
+  let mut verbatim = Vec::with_capacity(bytes.len() + 8);
+  match prefix.kind() {
+    Prefix::UNC(_, _) => {
+      verbatim.extend_from_slice(br"\\?\UNC\");
+      verbatim.extend_from_slice(&bytes[2..]);
+    }
+    _ => {
+      verbatim.extend_from_slice(br"\\?\");
+      verbatim.extend_from_slice(bytes);
+    }
+  }
+  // SAFETY: `verbatim` joins ASCII bytes with a suffix of `normalized`'s encoded
+  // bytes that starts after ASCII separators.
+  Cow::Owned(PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(verbatim) }))
+}
+
+pub(crate) fn strip_verbatim(path: &Path) -> Cow<'_, Path> {
+  let bytes = path.as_os_str().as_encoded_bytes();
+  let mut components = path.components();
+  let Some(Component::Prefix(prefix)) = components.next() else {
+    return Cow::Borrowed(path);
+  };
+  // Check the receiver's own components: verbatim parsing keeps `/`, `.`,
+  // and `..` literal, which reparsing the suffix as a Win32 path would not.
+  if !components_are_verbatim_stable(components) {
+    return Cow::Borrowed(path);
+  }
+
+  match prefix.kind() {
+    Prefix::VerbatimDisk(_) if bytes.starts_with(br"\\?\") => {
+      // SAFETY: the removed `\\?\` is ASCII, so the suffix starts on an
+      // encoded-character boundary.
+      Cow::Borrowed(Path::new(unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[4..]) }))
+    }
+    Prefix::VerbatimUNC(server, share)
+      if !server.is_empty()
+        && !share.is_empty()
+        && component_is_verbatim_stable(server)
+        && component_is_verbatim_stable(share) =>
+    {
+      let rest = &bytes[prefix.as_os_str().len()..];
+      let mut unc = Vec::with_capacity(server.len() + share.len() + rest.len() + 3);
+      unc.extend_from_slice(br"\\");
+      unc.extend_from_slice(server.as_encoded_bytes());
+      unc.push(b'\\');
+      unc.extend_from_slice(share.as_encoded_bytes());
+      unc.extend_from_slice(rest);
+      // SAFETY: `unc` joins ASCII separators with whole encoded components and
+      // the receiver's suffix after its prefix component.
+      Cow::Owned(PathBuf::from(unsafe { OsString::from_encoded_bytes_unchecked(unc) }))
+    }
+    _ => Cow::Borrowed(path),
+  }
+}
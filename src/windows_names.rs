@@ -0,0 +1,37 @@
+/// Returns `true` if a Win32 path component names a reserved DOS device.
+///
+/// Win32 ignores an extension and trailing spaces when matching device names,
+/// so `NUL`, `nul.txt`, and `COM1 .log` all open devices. Superscript digits
+/// count as port numbers. Matching is ASCII case-insensitive on the component's
+/// encoded bytes.
+pub(crate) fn is_windows_reserved_name(component: &[u8]) -> bool {
+  let stem = match memchr::memchr(b'.', component) {
+    Some(dot) => &component[..dot],
+    None => component,
+  };
+  let stem = stem.trim_ascii_end();
+  let Some((name, port)) = stem.split_at_checked(3) else {
+    return false;
+  };
+  if name.eq_ignore_ascii_case(b"CON") {
+    return port.is_empty()
+      || port.eq_ignore_ascii_case(b"IN$")
+      || port.eq_ignore_ascii_case(b"OUT$");
+  }
+  if name.eq_ignore_ascii_case(b"PRN")
+    || name.eq_ignore_ascii_case(b"AUX")
+    || name.eq_ignore_ascii_case(b"NUL")
+  {
+    return port.is_empty();
+  }
+  if name.eq_ignore_ascii_case(b"COM") || name.eq_ignore_ascii_case(b"LPT") {
+    // `¹`, `²`, and `³` are U+00B9, U+00B2, and U+00B3.
+    return matches!(port, [b'0'..=b'9'] | [0xC2, 0xB9 | 0xB2 | 0xB3]);
+  }
+  false
+}
+
+/// Returns `true` if Win32 would strip a trailing dot or space from a component.
+pub(crate) fn has_windows_trailing_dot_or_space(component: &[u8]) -> bool {
+  matches!(component.last(), Some(b'.' | b' '))
+}
@@ -9,8 +9,9 @@ use std::process::ExitCode;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use arcstr::ArcStr;
+use bumpalo::Bump;
 use mimalloc_safe::MiMalloc;
-use sugar_path::{SugarPath, SugarPathBuf};
+use sugar_path::{SugarBump, SugarPath, SugarPathBuf};
 
 const MEASUREMENTS: usize = 7;
 const TARGET_ENVIRONMENT: &str = if cfg!(target_env = "gnu") {
@@ -332,6 +333,22 @@ const SCENARIOS: &[Scenario] = &[
     name: "absolutize_with / clean relative input + owned cwd -> absolute path (setup excluded)",
     run: absolutize_with_clean_relative_owned_cwd,
   },
+  Scenario {
+    name: "absolutize / owned clean absolute input via owned receiver -> PathBuf",
+    run: absolutize_owned_clean_absolute_consuming,
+  },
+  Scenario {
+    name: "absolutize_with / owned clean relative input via owned receiver -> PathBuf",
+    run: absolutize_with_owned_clean_relative_consuming,
+  },
+  Scenario {
+    name: "absolutize_with / owned clean relative input with spare capacity via owned receiver -> PathBuf",
+    run: absolutize_with_owned_clean_relative_spare_consuming,
+  },
+  Scenario {
+    name: "absolutize_with / owned dirty relative input with spare capacity via owned receiver -> PathBuf",
+    run: absolutize_with_owned_dirty_relative_spare_consuming,
+  },
   Scenario {
     name: "relative / canonical native descendant -> natural result",
     run: relative_absolute,
@@ -340,6 +357,10 @@ const SCENARIOS: &[Scenario] = &[
     name: "relative / canonical native descendant -> PathBuf",
     run: relative_absolute_into_owned,
   },
+  Scenario {
+    name: "relative_with / canonical native descendant via owned receiver -> PathBuf",
+    run: relative_with_absolute_consuming,
+  },
   Scenario { name: "relative / relative inputs -> relative path", run: relative_relative },
   Scenario {
     name: "relative / dotted relative inputs -> relative path",
@@ -416,6 +437,38 @@ const SCENARIOS: &[Scenario] = &[
     name: "Windows / verbatim UNC different share -> relative path",
     run: windows_relative_verbatim_unc_different_share,
   },
+  Scenario {
+    name: "normalize_in / dirty input -> arena path (setup excluded)",
+    run: normalize_in_dirty,
+  },
+  Scenario {
+    name: "relative_with_in / canonical native descendant -> arena path (setup excluded)",
+    run: relative_with_in_absolute,
+  },
+  Scenario {
+    name: "relative_with_in / upward relation -> arena path (setup excluded)",
+    run: relative_with_in_upward,
+  },
+  Scenario {
+    name: "to_slash_in / native path -> arena slash text (setup excluded)",
+    run: to_slash_in_native,
+  },
+  Scenario {
+    name: "normalize_into / dirty input -> grown PathBuf (setup excluded)",
+    run: normalize_into_dirty,
+  },
+  Scenario {
+    name: "relative_into / canonical native descendant -> grown PathBuf (setup excluded)",
+    run: relative_into_absolute,
+  },
+  Scenario {
+    name: "relative_into / canonical native sibling -> grown PathBuf (setup excluded)",
+    run: relative_into_sibling,
+  },
+  Scenario {
+    name: "to_slash_into / native path -> grown String (setup excluded)",
+    run: to_slash_into_native,
+  },
   Scenario { name: "to_slash / native path -> slash text", run: to_slash_native },
   #[cfg(any(target_family = "unix", target_family = "windows"))]
   Scenario {
@@ -1009,6 +1062,53 @@ fn absolutize_with_clean_relative_owned_cwd(mode: RunMode) -> AllocationStats {
   )
 }
 
+fn absolutize_owned_clean_absolute_consuming(mode: RunMode) -> AllocationStats {
+  run_prepared(
+    mode,
+    || PathBuf::from(native_paths::ABSOLUTE_CLEAN),
+    |path| {
+      let value = black_box(path).into_absolutized();
+      black_box(value);
+    },
+  )
+}
+
+fn absolutize_with_owned_relative_consuming(
+  mode: RunMode,
+  setup: impl FnOnce() -> PathBuf,
+) -> AllocationStats {
+  run_prepared(mode, setup, |path| {
+    let cwd = black_box(Path::new(native_paths::ABSOLUTE_BASE));
+    let value = black_box(path).into_absolutized_with(cwd);
+    black_box(value);
+  })
+}
+
+/// Leaves room to write the cwd in front of `input` in the same buffer.
+fn path_with_spare_capacity(input: &str) -> PathBuf {
+  let mut path = PathBuf::with_capacity(native_paths::ABSOLUTE_BASE.len() + 1 + input.len());
+  path.push(input);
+  path
+}
+
+fn absolutize_with_owned_clean_relative_consuming(mode: RunMode) -> AllocationStats {
+  absolutize_with_owned_relative_consuming(mode, || {
+    PathBuf::from(native_paths::RELATIVE_CLEAN_INPUT)
+  })
+}
+
+fn absolutize_with_owned_clean_relative_spare_consuming(mode: RunMode) -> AllocationStats {
+  absolutize_with_owned_relative_consuming(mode, || {
+    path_with_spare_capacity(native_paths::RELATIVE_CLEAN_INPUT)
+  })
+}
+
+fn absolutize_with_owned_dirty_relative_spare_consuming(mode: RunMode) -> AllocationStats {
+  absolutize_with_owned_relative_consuming(mode, || {
+    path_with_spare_capacity(native_paths::RELATIVE_INPUT)
+  })
+}
+
 fn relative_absolute(mode: RunMode) -> AllocationStats {
   run_prepared(
     mode,
@@ -1034,6 +1134,117 @@ fn relative_absolute_into_owned(mode: RunMode) -> AllocationStats {
   )
 }
 
+fn relative_with_absolute_consuming(mode: RunMode) -> AllocationStats {
+  run_prepared(
+    mode,
+    || PathBuf::from(native_paths::ABSOLUTE_TARGET),
+    |path| {
+      let base = black_box(Path::new(native_paths::ABSOLUTE_BASE));
+      let value = black_box(path).into_relative_with(base, base);
+      black_box(value);
+    },
+  )
+}
+
+/// An arena with room for every arena scenario's result in its first chunk.
+fn arena() -> Bump {
+  Bump::with_capacity(1024)
+}
+
+fn normalize_in_dirty(mode: RunMode) -> AllocationStats {
+  run_prepared(mode, arena, |bump| {
+    let value = black_box(Path::new(native_paths::DIRTY)).normalize_in(&bump);
+    black_box(value);
+  })
+}
+
+fn relative_with_in_absolute(mode: RunMode) -> AllocationStats {
+  run_prepared(mode, arena, |bump| {
+    let base = black_box(Path::new(native_paths::ABSOLUTE_BASE));
+    let value =
+      black_box(Path::new(native_paths::ABSOLUTE_TARGET)).relative_with_in(base, base, &bump);
+    black_box(value);
+  })
+}
+
+fn relative_with_in_upward(mode: RunMode) -> AllocationStats {
+  run_prepared(mode, arena, |bump| {
+    let base = black_box(Path::new(native_paths::ROLLDOWN_PARENT_BASE));
+    let cwd = Path::new(native_paths::ROLLDOWN_CWD);
+    let value =
+      black_box(Path::new(native_paths::ROLLDOWN_PARENT_TARGET)).relative_with_in(base, cwd, &bump);
+    black_box(value);
+  })
+}
+
+/// Setup makes the first call so the measured call sees the steady state of a
+/// scratch buffer that already fits the result.
+fn normalize_into_dirty(mode: RunMode) -> AllocationStats {
+  let path = Path::new(native_paths::DIRTY);
+  run_prepared(
+    mode,
+    || {
+      let mut out = PathBuf::new();
+      path.normalize_into(&mut out);
+      out
+    },
+    |mut out| {
+      black_box(path).normalize_into(&mut out);
+      black_box(out);
+    },
+  )
+}
+
+fn relative_into_absolute(mode: RunMode) -> AllocationStats {
+  let target = Path::new(native_paths::ABSOLUTE_TARGET);
+  let base = Path::new(native_paths::ABSOLUTE_BASE);
+  run_prepared(
+    mode,
+    || {
+      let mut out = PathBuf::new();
+      target.relative_into(base, &mut out);
+      out
+    },
+    |mut out| {
+      black_box(target).relative_into(black_box(base), &mut out);
+      black_box(out);
+    },
+  )
+}
+
+fn relative_into_sibling(mode: RunMode) -> AllocationStats {
+  let target = Path::new(native_paths::ROLLDOWN_PARENT_TARGET);
+  let base = Path::new(native_paths::ROLLDOWN_PARENT_BASE);
+  run_prepared(
+    mode,
+    || {
+      let mut out = PathBuf::new();
+      target.relative_into(base, &mut out);
+      out
+    },
+    |mut out| {
+      black_box(target).relative_into(black_box(base), &mut out);
+      black_box(out);
+    },
+  )
+}
+
+fn to_slash_into_native(mode: RunMode) -> AllocationStats {
+  let path = Path::new(native_paths::ABSOLUTE_TARGET);
+  run_prepared(
+    mode,
+    || {
+      let mut out = String::new();
+      path.to_slash_into(&mut out);
+      out
+    },
+    |mut out| {
+      black_box(path).to_slash_into(&mut out);
+      black_box(out);
+    },
+  )
+}
+
 fn relative_relative(mode: RunMode) -> AllocationStats {
   run_prepared(
     mode,
@@ -1225,6 +1436,13 @@ fn windows_relative_verbatim_unc_different_share(mode: RunMode) -> AllocationSta
   )
 }
 
+fn to_slash_in_native(mode: RunMode) -> AllocationStats {
+  run_prepared(mode, arena, |bump| {
+    let value = black_box(Path::new(native_paths::ABSOLUTE_TARGET)).to_slash_in(&bump);
+    black_box(value);
+  })
+}
+
 fn to_slash_native(mode: RunMode) -> AllocationStats {
   run_prepared(
     mode,
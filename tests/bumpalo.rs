@@ -0,0 +1,62 @@
+#![cfg(feature = "bumpalo")]
+
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+use sugar_path::{SugarBump, SugarPath};
+
+fn native(slash: &str) -> PathBuf {
+  slash.split('/').collect()
+}
+
+#[test]
+fn normalize_in_matches_normalize() {
+  let bump = Bump::new();
+  for input in ["a/./b/../c", "./a//b/", "../x/../../y", "", ".", "a/..", "a/../", "src/lib.rs"] {
+    let path = native(input);
+    assert_eq!(path.normalize_in(&bump), path.normalize(), "{input:?}");
+    assert_eq!(path.as_os_str().normalize_in(&bump), path.normalize(), "{input:?}");
+  }
+  assert_eq!("".normalize_in(&bump), Path::new("."));
+}
+
+#[test]
+fn results_live_in_the_arena() {
+  let bump = Bump::new();
+  let normalized = {
+    let input = native("pkg/./lib/../bin");
+    input.normalize_in(&bump)
+  };
+  assert_eq!(normalized, native("pkg/bin"));
+  let bytes = normalized.as_os_str().as_encoded_bytes();
+  // SAFETY: the chunks are only compared by address, never read.
+  let mut chunks = unsafe { bump.iter_allocated_chunks_raw() };
+  assert!(chunks.any(|(start, len)| {
+    let chunk = start.addr()..start.addr() + len;
+    chunk.contains(&bytes.as_ptr().addr())
+  }));
+}
+
+#[test]
+fn relative_with_in_matches_relative_with() {
+  let bump = Bump::new();
+  let cwd = std::env::current_dir().unwrap();
+  let cases = [
+    (cwd.join("a").join("b"), cwd.join("a").join("c")),
+    (cwd.join("a").join("b"), cwd.join("a")),
+    (native("x/y"), native("x")),
+    (native("x"), cwd.join("z")),
+  ];
+
+  for (target, base) in cases {
+    let expected = target.relative_with(&base, &cwd);
+    assert_eq!(target.relative_with_in(&base, &cwd, &bump), expected, "{target:?} from {base:?}");
+  }
+}
+
+#[test]
+fn to_slash_in_converts_native_separators() {
+  let bump = Bump::new();
+  assert_eq!(native("src/module_loader/mod.rs").to_slash_in(&bump), "src/module_loader/mod.rs");
+  assert_eq!("a/b".to_slash_in(&bump), "a/b");
+}
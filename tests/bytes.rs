@@ -0,0 +1,266 @@
+use std::borrow::Cow;
+
+use sugar_path::{
+  SugarPath,
+  bytes::{Syntax, normalize, relative_with, to_slash},
+};
+
+fn assert_normalizes(syntax: Syntax, cases: &[(&str, &str)]) {
+  for &(input, expected) in cases {
+    let normalized = normalize(input.as_bytes(), syntax);
+    assert_eq!(normalized, expected.as_bytes(), "{syntax:?} {input:?}");
+    assert_eq!(normalize(&normalized, syntax), expected.as_bytes(), "{syntax:?} {expected:?}");
+  }
+}
+
+#[test]
+fn unix_syntax_normalizes_on_every_host() {
+  assert_normalizes(
+    Syntax::Unix,
+    &[
+      ("", "."),
+      ("./", "./"),
+      ("/foo/../../../bar", "/bar"),
+      ("a//b//./c", "a/b/c"),
+      ("///..//./foo/.//bar", "/foo/bar"),
+      ("bar/foo../../", "bar/"),
+      ("../foo../../../bar", "../../bar"),
+      ("a/b/c/../../../", "./"),
+      ("a/b/c/../../..", "."),
+      ("foo/bar\\baz", "foo/bar\\baz"),
+      ("C:/x/../y", "C:/y"),
+    ],
+  );
+}
+
+#[test]
+fn windows_syntax_normalizes_on_every_host() {
+  assert_normalizes(
+    Syntax::Windows,
+    &[
+      ("", "."),
+      ("./fixtures///b/../b/c.js", r"fixtures\b\c.js"),
+      ("/foo/../../../bar", r"\bar"),
+      ("//server/share/dir/file.ext", r"\\server\share\dir\file.ext"),
+      ("C:", "C:."),
+      ("C:/", r"C:\"),
+      ("c:/../a", r"c:\a"),
+      (r"C:..\..\abc\..\def", r"C:..\..\def"),
+      (r"C:.\file", "C:file"),
+      (r"C:.\..", "C:.."),
+      ("file:stream", "file:stream"),
+      (r"bar\foo..\..\", r"bar\"),
+      ("foo/bar\\baz", r"foo\bar\baz"),
+      (r"dir\..\C:foo", r".\C:foo"),
+      (r"dir\..\C:", r".\C:"),
+    ],
+  );
+}
+
+#[test]
+fn windows_syntax_keeps_prefix_semantics() {
+  assert_normalizes(
+    Syntax::Windows,
+    &[
+      (r"\\?\C:", r"\\?\C:"),
+      (r"\\?\c:\a\..\b", r"\\?\c:\b"),
+      (r"\\server\share", r"\\server\share\"),
+      (r"\\?\UNC\server\share\.", r"\\?\UNC\server\share"),
+      (r"\\?\UNC\server\share\foo\..\", r"\\?\UNC\server\share\"),
+      (r"\\.\PIPE", r"\\.\PIPE"),
+      (r"//./PIPE/foo/..", r"\\.\PIPE"),
+      (r"\\.\PIPE\foo\..\", r"\\.\PIPE\"),
+      (r"\\?\Volume{abc}\foo\..", r"\\?\Volume{abc}"),
+      (r"\\?\C:\foo/", r"\\?\C:\foo/"),
+      (r"\\?\C:\a/..\b", r"\\?\C:\a/..\b"),
+      (r"\\?\C:\a/b\..\c", r"\\?\C:\c"),
+      (r"\\server", r"\server"),
+    ],
+  );
+}
+
+#[test]
+fn clean_input_is_borrowed() {
+  for (path, syntax) in [
+    (&b"a/b/"[..], Syntax::Unix),
+    (b"../../x", Syntax::Unix),
+    (b".", Syntax::Unix),
+    (br"C:\foo", Syntax::Windows),
+    (br"..\foo\", Syntax::Windows),
+    (b"C:.", Syntax::Windows),
+  ] {
+    let Cow::Borrowed(borrowed) = normalize(path, syntax) else {
+      panic!("expected a borrowed result for {path:?}");
+    };
+    assert_eq!(borrowed.as_ptr(), path.as_ptr());
+  }
+  assert!(matches!(normalize(b"", Syntax::Unix), Cow::Borrowed(b".")));
+  assert!(matches!(normalize(b"a/./b", Syntax::Unix), Cow::Owned(_)));
+}
+
+#[test]
+fn non_utf8_components_are_copied_unchanged() {
+  assert_eq!(normalize(b"\xFF/./x/../\xFE", Syntax::Unix), &b"\xFF/\xFE"[..]);
+  assert_eq!(normalize(b"\xFF/./x/../\xFE", Syntax::Windows), &b"\xFF\\\xFE"[..]);
+}
+
+#[test]
+fn relative_with_relates_normalized_unix_paths() {
+  for (target, base, cwd, expected) in [
+    ("/srv/app/bin", "/srv/lib", "", "../app/bin"),
+    ("/srv/./app//bin/", "/srv/lib/../app", "", "bin"),
+    ("/srv", "/srv/app/bin", "", "../.."),
+    ("/srv/app", "/srv/app/", "", ""),
+    ("/../a", "/b", "", "../a"),
+    ("../a/b", "../a", "", "b"),
+    ("a", "b", "", "../a"),
+    ("a", "../b", "/w/x", "../x/a"),
+    ("../../a", "b", "/w/x", "../../../a"),
+    ("a", "/w", "/w/x", "x/a"),
+    ("/w", "a", "/w/x", "../.."),
+    ("a\\b", "a", "", "../a\\b"),
+  ] {
+    assert_eq!(
+      relative_with(target.as_bytes(), base.as_bytes(), cwd.as_bytes()),
+      expected.as_bytes(),
+      "{target:?} from {base:?} in {cwd:?}"
+    );
+  }
+}
+
+#[test]
+fn relative_with_borrows_contiguous_target_suffixes() {
+  for (target, base, expected) in [
+    (&b"/srv/app/bin"[..], &b"/srv"[..], &b"app/bin"[..]),
+    (b"/srv/app/bin/", b"/srv/./", b"app/bin"),
+    (b"src/lib.rs", b"src", b"lib.rs"),
+    (b"/srv", b"/srv", b""),
+  ] {
+    let Cow::Borrowed(borrowed) = relative_with(target, base, b"/") else {
+      panic!("expected a borrowed result for {target:?}");
+    };
+    assert_eq!(borrowed, expected);
+  }
+  assert!(matches!(relative_with(b"/srv/app//bin", b"/srv", b"/"), Cow::Owned(_)));
+  assert!(matches!(relative_with(b"/srv/app/x/../bin", b"/srv", b"/"), Cow::Owned(_)));
+}
+
+#[test]
+#[should_panic(expected = "explicit current directory must be absolute")]
+fn relative_with_panics_when_needed_cwd_is_relative() {
+  let _ = relative_with(b"a", b"../b", b"w");
+}
+
+#[test]
+fn relative_with_ignores_unneeded_cwd() {
+  assert_eq!(relative_with(b"/a/b", b"/a", b"w"), &b"b"[..]);
+  assert_eq!(relative_with(b"../a/b", b"../a", b""), &b"b"[..]);
+}
+
+#[test]
+fn to_slash_replaces_windows_separators_only() {
+  assert_eq!(to_slash(br"\\?\C:\a/b\c", Syntax::Windows), &b"//?/C:/a/b/c"[..]);
+  assert_eq!(to_slash(b"\xFF\\x", Syntax::Windows), &b"\xFF/x"[..]);
+  assert!(matches!(to_slash(b"a/b", Syntax::Windows), Cow::Borrowed(b"a/b")));
+  assert!(matches!(to_slash(br"a\b", Syntax::Unix), Cow::Borrowed(br"a\b")));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_relative_with_matches_path_relative_with_for_short_arbitrary_bytes() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+  const ALPHABET: [u8; 4] = [b'a', b'/', b'.', 0xFF];
+  let mut inputs = vec![Vec::new()];
+  for _ in 0..4 {
+    let longer: Vec<_> = inputs
+      .iter()
+      .filter(|input| input.len() == inputs.last().unwrap().len())
+      .flat_map(|input| {
+        ALPHABET.iter().map(move |&byte| {
+          let mut input = input.clone();
+          input.push(byte);
+          input
+        })
+      })
+      .collect();
+    inputs.extend(longer);
+  }
+
+  for cwd in [&b"/"[..], b"/a/\xFF"] {
+    let cwd_path = Path::new(OsStr::from_bytes(cwd));
+    for target in &inputs {
+      for base in &inputs {
+        let expected =
+          Path::new(OsStr::from_bytes(target)).relative_with(OsStr::from_bytes(base), cwd_path);
+        let actual = relative_with(target, base, cwd);
+        assert_eq!(actual, expected.as_os_str().as_bytes(), "{target:?} from {base:?} in {cwd:?}");
+      }
+    }
+  }
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_native_syntax_matches_path_normalize_for_short_arbitrary_bytes() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+  const ALPHABET: [u8; 4] = [b'a', b'/', b'.', 0xFF];
+  let mut inputs = vec![Vec::new()];
+  for _ in 0..6 {
+    let longer: Vec<_> = inputs
+      .iter()
+      .filter(|input| input.len() == inputs.last().unwrap().len())
+      .flat_map(|input| {
+        ALPHABET.iter().map(move |&byte| {
+          let mut input = input.clone();
+          input.push(byte);
+          input
+        })
+      })
+      .collect();
+    inputs.extend(longer);
+  }
+
+  assert_eq!(Syntax::NATIVE, Syntax::Unix);
+  for input in inputs {
+    let expected = Path::new(OsStr::from_bytes(&input)).normalize();
+    let actual = normalize(&input, Syntax::NATIVE);
+    assert_eq!(actual, expected.as_os_str().as_bytes(), "{input:?}");
+    assert_eq!(
+      matches!(actual, Cow::Borrowed(_)),
+      matches!(expected, Cow::Borrowed(_)),
+      "{input:?}"
+    );
+  }
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_native_syntax_matches_path_normalize_for_short_strings() {
+  use std::path::Path;
+
+  const ALPHABET: [char; 6] = ['a', '\\', '/', '.', ':', '?'];
+  let mut inputs = vec![String::new()];
+  for _ in 0..5 {
+    let longer: Vec<_> = inputs
+      .iter()
+      .filter(|input| input.len() == inputs.last().unwrap().len())
+      .flat_map(|input| ALPHABET.iter().map(move |&c| format!("{input}{c}")))
+      .collect();
+    inputs.extend(longer);
+  }
+  let seeded: Vec<_> = [r"\\?\", r"\\.\", r"\\s\t", r"\\?\UNC\s\t", "C:"]
+    .iter()
+    .flat_map(|seed| {
+      inputs.iter().filter(|input| input.len() <= 3).map(move |i| format!("{seed}{i}"))
+    })
+    .collect();
+
+  assert_eq!(Syntax::NATIVE, Syntax::Windows);
+  for input in inputs.iter().chain(&seeded) {
+    let expected = Path::new(input).normalize();
+    let actual = normalize(input.as_bytes(), Syntax::NATIVE);
+    assert_eq!(actual, expected.as_os_str().as_encoded_bytes(), "{input:?}");
+  }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "camino")]
+
+use std::borrow::Cow;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use sugar_path::{SugarPath, SugarUtf8Path};
+
+fn native(slash: &str) -> Utf8PathBuf {
+  slash.split('/').collect()
+}
+
+#[test]
+fn normalize_matches_sugar_path_and_borrows_when_unchanged() {
+  for input in ["a/./b/../c", "./a//b/", "../x/../../y", "", "a/.."] {
+    let path = native(input);
+    let utf8 = path.normalize();
+    assert_eq!(utf8.as_std_path(), path.as_std_path().normalize(), "{input:?}");
+  }
+
+  let clean = native("src/lib.rs");
+  assert!(matches!(clean.normalize(), Cow::Borrowed(_)));
+  assert!(matches!(native("src/./lib.rs").normalize(), Cow::Owned(_)));
+}
+
+#[test]
+fn explicit_cwd_methods_match_sugar_path() {
+  let cwd = Utf8PathBuf::from_path_buf(std::env::current_dir().unwrap()).unwrap();
+  let path = native("src/../lib.rs");
+  assert_eq!(
+    path.absolutize_with(&cwd).as_std_path(),
+    path.as_std_path().absolutize_with(cwd.as_std_path())
+  );
+
+  let target = cwd.join(native("a/b/c"));
+  let base = cwd.join(native("a/d"));
+  let relative = target.relative_with(&base, &cwd);
+  assert_eq!(relative.as_std_path(), target.as_std_path().relative_with(&base, &cwd));
+  assert_eq!(relative.to_slash(), "../b/c");
+}
+
+#[test]
+fn ambient_cwd_methods_return_utf8_results() {
+  let cwd = Utf8PathBuf::from_path_buf(std::env::current_dir().unwrap()).unwrap();
+  let path = native("target/out.txt");
+  assert_eq!(path.absolutize(), cwd.join(&path));
+  assert_eq!(path.try_absolutize().unwrap(), cwd.join(&path));
+
+  let absolute = cwd.join(native("a/b"));
+  assert_eq!(absolute.relative(&cwd), native("a/b"));
+  assert_eq!(absolute.try_relative(cwd.join("a")).unwrap(), Utf8Path::new("b"));
+}
+
+#[test]
+fn to_slash_borrows_slash_paths() {
+  let path = Utf8Path::new("a/b/c");
+  assert!(matches!(path.to_slash(), Cow::Borrowed("a/b/c")));
+  assert_eq!(native("x/y").to_slash(), "x/y");
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_results_use_native_separators() {
+  let path = Utf8Path::new("C:/work/./src/../lib.rs");
+  assert_eq!(path.normalize(), Utf8Path::new(r"C:\work\lib.rs"));
+  assert_eq!(Utf8Path::new(r"C:\work\src").to_slash(), "C:/work/src");
+}
@@ -0,0 +1,54 @@
+use sugar_path::{
+  bytes::{Syntax, normalize},
+  const_normalize,
+};
+
+const WORKSPACE: &str = "/workspace/./crates//rolldown/../";
+const WORKSPACE_NORMALIZED: &str = const_normalize!(WORKSPACE);
+static SRC: &str = const_normalize!("./crates/rolldown/src/../src");
+
+macro_rules! assert_const_normalizes {
+  ($($input:literal => $expected:literal),* $(,)?) => {
+    $(
+      let normalized: &'static str = const_normalize!($input);
+      assert_eq!(normalized, $expected, "{:?}", $input);
+      assert_eq!(normalized.as_bytes(), &*normalize($input.as_bytes(), Syntax::Unix), "{:?}", $input);
+    )*
+  };
+}
+
+#[test]
+fn const_normalize_matches_unix_syntax_normalize() {
+  assert_const_normalizes!(
+    "" => ".",
+    "." => ".",
+    "./" => "./",
+    "/" => "/",
+    "//" => "/",
+    "/foo/../../../bar" => "/bar",
+    "a//b//./c" => "a/b/c",
+    "bar/foo../../" => "bar/",
+    "../foo../../../bar" => "../../bar",
+    "a/b/c/../../../" => "./",
+    "a/b/c/../../.." => ".",
+    r"foo\..\bar" => r"foo\..\bar",
+    "模块/./任务.rs" => "模块/任务.rs",
+  );
+}
+
+#[test]
+fn const_normalize_initializes_const_and_static_items() {
+  assert_eq!(WORKSPACE_NORMALIZED, "/workspace/crates/");
+  assert_eq!(SRC, "crates/rolldown/src");
+}
+
+#[cfg(all(feature = "std", not(target_family = "windows")))]
+#[test]
+fn const_normalize_matches_native_normalize_on_unix_hosts() {
+  use std::path::Path;
+
+  use sugar_path::SugarPath;
+
+  assert_eq!(Path::new(WORKSPACE).normalize().to_str(), Some(WORKSPACE_NORMALIZED));
+  assert_eq!(Path::new("./crates/rolldown/src/../src").normalize().to_str(), Some(SRC));
+}
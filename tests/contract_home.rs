@@ -0,0 +1,115 @@
+use std::{borrow::Cow, path::Path};
+
+use sugar_path::SugarPath;
+mod test_utils;
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_contract_home_matches_whole_normalized_components() {
+  let home = "/home/alice";
+  assert_eq_str!("/home/alice/project/src/a.rs".contract_home(home), "~/project/src/a.rs");
+  assert_eq_str!("/home/alice/./project/../notes/".contract_home("/home/alice/"), "~/notes");
+  assert_eq_str!("/home/alice".contract_home(home), "~");
+  assert_eq_str!("/home/alice/".contract_home(home), "~");
+  assert_eq_str!("/home/alice2/x".contract_home(home), "/home/alice2/x");
+  assert_eq_str!("/home/Alice/x".contract_home(home), "/home/Alice/x");
+  assert_eq_str!("/home/x/../alice/y".contract_home(home), "~/y");
+  assert_eq_str!("/etc/./hosts".contract_home(home), "/etc/hosts");
+  assert_eq_str!("alice/x".contract_home(home), "alice/x");
+}
+
+#[test]
+fn empty_or_relative_home_contracts_nothing() {
+  #[cfg(target_family = "unix")]
+  let absolute = "/home/alice/x";
+  #[cfg(target_family = "windows")]
+  let absolute = r"C:\Users\alice\x";
+
+  for home in ["", ".", "./", "alice", "../alice"] {
+    assert_eq!("src/lib.rs".contract_home(home), Path::new("src/lib.rs"), "{home:?}");
+    assert_eq!("alice/x".contract_home(home), Path::new("alice/x"), "{home:?}");
+    assert_eq!(absolute.contract_home(home), Path::new(absolute), "{home:?}");
+  }
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_contract_home_ignores_ascii_case_and_root_spelling() {
+  let home = r"C:\Users\alice";
+  assert_eq_str!(r"c:\users\ALICE\project\a.rs".contract_home(home), r"~\project\a.rs");
+  assert_eq_str!("C:/Users/alice/x".contract_home(home), r"~\x");
+  assert_eq_str!(r"C:\Users\alice".contract_home(home), "~");
+  assert_eq_str!(r"D:\Users\alice\x".contract_home(home), r"D:\Users\alice\x");
+  assert_eq_str!(r"\\?\C:\Users\alice\x".contract_home(home), r"\\?\C:\Users\alice\x");
+  assert_eq_str!(r"\\server\share\alice\x".contract_home(r"\\SERVER\share\alice"), r"~\x");
+  assert_eq_str!(r"C:\Users\alice\D:data".contract_home(home), r"~\D:data");
+}
+
+#[test]
+fn contract_home_borrows_unmatched_normalized_receivers() {
+  #[cfg(target_family = "unix")]
+  let (input, home) = ("/srv/data/x", "/home/alice");
+  #[cfg(target_family = "windows")]
+  let (input, home) = (r"D:\data\x", r"C:\Users\alice");
+
+  let Cow::Borrowed(contracted) = input.contract_home(home) else {
+    panic!("expected an unmatched clean receiver to borrow");
+  };
+  assert_eq!(contracted.as_os_str().as_encoded_bytes().as_ptr(), input.as_ptr());
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_contract_home_with_picks_the_shortest_spelling() {
+  let home = "/home/alice";
+  let cwd = "/home/alice/work/project";
+
+  assert_eq_str!("src/a.rs".contract_home_with(home, cwd), "src/a.rs");
+  assert_eq_str!("/home/alice/work/project/src/a.rs".contract_home_with(home, cwd), "src/a.rs");
+  assert_eq_str!("/home/alice/notes.md".contract_home_with(home, cwd), "~/notes.md");
+  assert_eq_str!("../other/x".contract_home_with(home, cwd), "../other/x");
+  assert_eq_str!(
+    "/home/alice/.config/app/settings.toml".contract_home_with(home, cwd),
+    "~/.config/app/settings.toml"
+  );
+  assert_eq_str!("/etc/hosts".contract_home_with(home, cwd), "/etc/hosts");
+  assert_eq_str!(".".contract_home_with(home, cwd), ".");
+  assert_eq_str!(cwd.contract_home_with(home, cwd), ".");
+  assert_eq_str!("/home/alice".contract_home_with(home, "/"), "~");
+  // Equal lengths prefer the contracted spelling over the relative one.
+  assert_eq_str!("/home/alice/ab".contract_home_with(home, "/home/alice/x"), "~/ab");
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_contract_home_with_picks_the_shortest_spelling() {
+  let home = r"C:\Users\alice";
+  let cwd = r"C:\Users\alice\work\project";
+
+  assert_eq_str!(r"src\a.rs".contract_home_with(home, cwd), r"src\a.rs");
+  assert_eq_str!(r"c:\users\alice\notes.md".contract_home_with(home, cwd), r"~\notes.md");
+  assert_eq_str!(r"D:\data\x".contract_home_with(home, cwd), r"D:\data\x");
+}
+
+#[test]
+fn contract_home_with_borrows_descendant_suffixes_of_the_receiver() {
+  #[cfg(target_family = "unix")]
+  let (input, home, cwd, expected) =
+    ("/home/alice/project/src/lib.rs", "/home/alice", "/home/alice/project", "src/lib.rs");
+  #[cfg(target_family = "windows")]
+  let (input, home, cwd, expected) = (
+    r"C:\Users\alice\project\src\lib.rs",
+    r"C:\Users\alice",
+    r"C:\Users\alice\project",
+    r"src\lib.rs",
+  );
+
+  let Cow::Borrowed(display) = input.contract_home_with(home, cwd) else {
+    panic!("expected a clean descendant to borrow");
+  };
+  assert_eq!(display, Path::new(expected));
+  assert_eq!(
+    display.as_os_str().as_encoded_bytes().as_ptr(),
+    input[input.len() - expected.len()..].as_ptr()
+  );
+}
@@ -0,0 +1,133 @@
+use std::{borrow::Cow, path::Path};
+
+use sugar_path::{DisplayPath, DisplayPolicy};
+mod test_utils;
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_preferred_policy_picks_relative_then_home_then_absolute() {
+  let display = DisplayPath::new("/home/alice/project").home("/home/alice");
+  assert_eq!(display.to_slash("/home/alice/project/src/a.rs"), "src/a.rs");
+  assert_eq!(display.to_slash("src/./a.rs"), "src/a.rs");
+  assert_eq!(display.to_slash("/home/alice/project"), ".");
+  assert_eq!(display.to_slash("/home/alice/notes.md"), "~/notes.md");
+  assert_eq!(display.to_slash("../other/b.rs"), "~/other/b.rs");
+  assert_eq!(display.to_slash("/home/alice"), "~");
+  assert_eq!(display.to_slash("/etc/hosts"), "/etc/hosts");
+  assert_eq!(display.to_slash("../../bob/c.rs"), "/home/bob/c.rs");
+
+  let without_home = DisplayPath::new("/home/alice/project");
+  assert_eq!(without_home.to_slash("/home/alice/notes.md"), "/home/alice/notes.md");
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_preferred_policy_picks_relative_then_home_then_absolute() {
+  let display = DisplayPath::new(r"C:\Users\alice\project").home(r"C:\Users\alice");
+  assert_eq_str!(display.display(r"c:\users\alice\project\src\a.rs"), r"src\a.rs");
+  assert_eq_str!(display.display(r"C:\Users\alice\notes.md"), r"~\notes.md");
+  assert_eq_str!(display.display(r"D:\data\x"), r"D:\data\x");
+  assert_eq!(display.to_slash(r"C:\Users\alice\notes.md"), "~/notes.md");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_shortest_and_absolute_policies() {
+  let shortest =
+    DisplayPath::new("/home/alice/project").home("/home/alice").policy(DisplayPolicy::Shortest);
+  assert_eq!(shortest.to_slash("../x"), "~/x");
+  assert_eq!(
+    shortest.to_slash("/home/alice/.config/app/settings.toml"),
+    "~/.config/app/settings.toml"
+  );
+  assert_eq!(shortest.to_slash("/etc/hosts"), "/etc/hosts");
+
+  let shortest_without_home = DisplayPath::new("/a/b/c").policy(DisplayPolicy::Shortest);
+  assert_eq!(shortest_without_home.to_slash("/a/x"), "/a/x");
+  assert_eq!(shortest_without_home.to_slash("/a/b/c/d"), "d");
+  assert_eq!(shortest_without_home.to_slash("/a/b/x/y"), "../x/y");
+
+  let absolute =
+    DisplayPath::new("/home/alice/project").home("/home/alice").policy(DisplayPolicy::Absolute);
+  assert_eq!(absolute.to_slash("src/a.rs"), "/home/alice/project/src/a.rs");
+  assert_eq!(absolute.to_slash("/home/alice/notes.md"), "/home/alice/notes.md");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_max_width_elides_middle_components() {
+  let display = DisplayPath::new("/home/alice/project").home("/home/alice");
+  let path = "src/runtime/module_loader/module_task.rs";
+  assert_eq!(display.clone().max_width(40).to_slash(path), path);
+  assert_eq!(
+    display.clone().max_width(39).to_slash(path),
+    "…/runtime/module_loader/module_task.rs"
+  );
+  assert_eq!(display.clone().max_width(36).to_slash(path), "src/…/module_loader/module_task.rs");
+  assert_eq!(display.clone().max_width(32).to_slash(path), "…/module_loader/module_task.rs");
+  assert_eq!(display.clone().max_width(20).to_slash(path), "src/…/module_task.rs");
+  assert_eq!(display.clone().max_width(5).to_slash(path), "…/module_task.rs");
+
+  let narrow = display.max_width(24);
+  assert_eq!(
+    narrow.to_slash("/home/alice/src/components/button/styles/index.css"),
+    "~/…/styles/index.css"
+  );
+  assert_eq!(narrow.to_slash("/usr/share/doc/example/readme.md"), "/…/doc/example/readme.md");
+  assert_eq!(narrow.to_slash("/a_very_long_directory_name.txt"), "/a_very_long_directory_name.txt");
+  assert_eq!(
+    narrow.max_width(20).to_slash("/home/alice/project/ünïcödé/ünïcödé/ünïcödé"),
+    "…/ünïcödé/ünïcödé"
+  );
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_max_width_keeps_the_drive_root() {
+  let display = DisplayPath::new(r"C:\work").max_width(20);
+  assert_eq_str!(display.display(r"D:\data\archive\2024\report.csv"), r"D:\…\2024\report.csv");
+  assert_eq!(display.to_slash(r"D:\data\archive\2024\report.csv"), "D:/…/2024/report.csv");
+}
+
+#[test]
+fn display_borrows_clean_descendant_suffixes() {
+  #[cfg(target_family = "unix")]
+  let (cwd, input, expected) = ("/work", "/work/src/lib.rs", "src/lib.rs");
+  #[cfg(target_family = "windows")]
+  let (cwd, input, expected) = (r"C:\work", r"C:\work\src\lib.rs", r"src\lib.rs");
+
+  let display = DisplayPath::new(cwd).max_width(80);
+  let Cow::Borrowed(shown) = display.display(input) else {
+    panic!("expected a clean descendant to borrow");
+  };
+  assert_eq!(shown, Path::new(expected));
+  #[cfg(target_family = "unix")]
+  assert!(matches!(display.to_slash_lossy(input), Cow::Borrowed("src/lib.rs")));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_slash_conversion_policies_for_invalid_unicode() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+  let display = DisplayPath::new("/work");
+  let path = Path::new(OsStr::from_bytes(b"/work/src/\xFF.rs"));
+  assert_eq!(display.try_to_slash(path), None);
+  assert_eq!(display.to_slash_lossy(path), "src/\u{FFFD}.rs");
+  assert_eq!(display.max_width(6).to_slash_lossy(path), "…/\u{FFFD}.rs");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+#[should_panic(expected = "path is not valid Unicode")]
+fn unix_strict_slash_conversion_panics_for_invalid_unicode() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+  let _ = DisplayPath::new("/work").to_slash(Path::new(OsStr::from_bytes(b"/work/\xFF")));
+}
+
+#[test]
+#[should_panic(expected = "the display cwd must be absolute")]
+fn new_rejects_relative_cwd() {
+  let _ = DisplayPath::new("relative");
+}
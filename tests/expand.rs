@@ -0,0 +1,100 @@
+use std::{borrow::Cow, path::Path};
+
+use sugar_path::{ExpandEnv, ExpandError, SugarPath};
+mod test_utils;
+
+fn unknown(name: &str) -> ExpandError {
+  ExpandError::UnknownVariable { name: name.to_owned() }
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_expand_with_substitutes_home_and_variables_then_normalizes() {
+  let env = ExpandEnv::new()
+    .home("/home/alice")
+    .var("HOME", "/home/alice")
+    .var("XDG_CONFIG_HOME", "/home/alice/.config/")
+    .var("NAME", "app")
+    .var("UP", "..");
+
+  assert_eq_str!("~".expand_with(&env).unwrap(), "/home/alice");
+  assert_eq_str!("~/cache".expand_with(&env).unwrap(), "/home/alice/cache");
+  assert_eq_str!("$HOME/x".expand_with(&env).unwrap(), "/home/alice/x");
+  assert_eq_str!("${XDG_CONFIG_HOME}/y".expand_with(&env).unwrap(), "/home/alice/.config/y");
+  assert_eq_str!("/srv/${NAME}_data/$NAME.log".expand_with(&env).unwrap(), "/srv/app_data/app.log");
+  assert_eq_str!("/srv/a/$UP/b/".expand_with(&env).unwrap(), "/srv/b/");
+  assert_eq_str!("~alice/x".expand_with(&env).unwrap(), "~alice/x");
+  assert_eq_str!("a/~/b".expand_with(&env).unwrap(), "a/~/b");
+  assert_eq_str!("cost$/$1/${a-b}/%NAME%".expand_with(&env).unwrap(), "cost$/$1/${a-b}/%NAME%");
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_expand_with_supports_percent_variables_and_ignores_name_case() {
+  let env = ExpandEnv::new()
+    .home(r"C:\Users\alice")
+    .var("AppData", r"C:\Users\alice\AppData\Roaming")
+    .var("NAME", "app");
+
+  assert_eq_str!(r"~\cache".expand_with(&env).unwrap(), r"C:\Users\alice\cache");
+  assert_eq_str!("~/cache".expand_with(&env).unwrap(), r"C:\Users\alice\cache");
+  assert_eq_str!(r"%APPDATA%\z".expand_with(&env).unwrap(), r"C:\Users\alice\AppData\Roaming\z");
+  assert_eq_str!(
+    r"%appdata%\$name".expand_with(&env).unwrap(),
+    r"C:\Users\alice\AppData\Roaming\app"
+  );
+  assert_eq_str!(r"100%\a%b\c%".expand_with(&env).unwrap(), r"100%\a%b\c%");
+  assert_eq!(r"%MISSING%\x".expand_with(&env), Err(unknown("MISSING")));
+}
+
+#[test]
+fn expand_with_reports_typed_errors() {
+  let env = ExpandEnv::new().var("SET", "value");
+
+  assert_eq!("$UNSET/x".expand_with(&env), Err(unknown("UNSET")));
+  assert_eq!("x/${UNSET}".expand_with(&env), Err(unknown("UNSET")));
+  assert_eq!("~/x".expand_with(&env), Err(ExpandError::MissingHome));
+  assert_eq!("${SET".expand_with(&env), Err(ExpandError::UnclosedBrace));
+  assert_eq!(unknown("UNSET").to_string(), "environment variable `UNSET` is not set");
+}
+
+#[test]
+fn unexpanded_paths_borrow_the_normalized_receiver() {
+  let env = ExpandEnv::new().home("home");
+  let input = "dist/assets";
+  let Ok(Cow::Borrowed(expanded)) = input.expand_with(&env) else {
+    panic!("expected an unexpanded clean path to borrow");
+  };
+  assert_eq!(expanded.as_os_str().as_encoded_bytes().as_ptr(), input.as_ptr());
+  assert!(matches!("~".expand_with(&env), Ok(Cow::Owned(_))));
+}
+
+#[test]
+fn environments_collect_from_pairs_and_later_values_win() {
+  let mut env: ExpandEnv = [("A", "first"), ("B", "b")].into_iter().collect();
+  env.extend([("A", "second")]);
+  assert_eq!("$A-$B".expand_with(&env).unwrap(), Path::new("second-b"));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_non_utf8_values_are_preserved() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+  let env = ExpandEnv::new().var("RAW", OsStr::from_bytes(b"na\xffme"));
+  let expanded = "/data/$RAW/x".expand_with(&env).unwrap();
+  assert_eq!(expanded.as_os_str().as_bytes(), b"/data/na\xffme/x");
+}
+
+#[test]
+fn expand_reads_the_process_environment() {
+  assert_eq!(
+    "$SUGAR_PATH_EXPAND_TEST_UNSET_VARIABLE".expand(),
+    Err(unknown("SUGAR_PATH_EXPAND_TEST_UNSET_VARIABLE"))
+  );
+  match std::env::home_dir() {
+    Some(home) => assert_eq!("~".expand().unwrap(), home.normalize()),
+    None => assert_eq!("~".expand(), Err(ExpandError::MissingHome)),
+  }
+  assert_eq!("dist".expand().unwrap(), Path::new("dist"));
+}
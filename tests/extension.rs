@@ -0,0 +1,105 @@
+use std::{ffi::OsStr, path::Path};
+
+use sugar_path::SugarPath;
+mod test_utils;
+
+fn split<'a>(path: &'a str, known: &[&str]) -> Option<(&'a OsStr, &'a OsStr)> {
+  path.split_extension_with(known)
+}
+
+fn os(text: &str) -> &OsStr {
+  OsStr::new(text)
+}
+
+#[test]
+fn split_extension_with_prefers_the_longest_known_extension() {
+  let known = ["ts", "d.ts", "tar.gz", "module.css"];
+  assert_eq!(split("foo.d.ts", &known), Some((os("foo"), os("d.ts"))));
+  assert_eq!(split("foo.ts", &known), Some((os("foo"), os("ts"))));
+  assert_eq!(split("backup.tar.gz", &known), Some((os("backup"), os("tar.gz"))));
+  assert_eq!(split("button.module.css", &known), Some((os("button"), os("module.css"))));
+  assert_eq!(split("jquery.min.js", &known), Some((os("jquery.min"), os("js"))));
+  assert_eq!(split("foo.xd.ts", &known), Some((os("foo.xd"), os("ts"))));
+  assert_eq!(split("Makefile", &known), None);
+  assert_eq!(split("foo.", &known), Some((os("foo"), os(""))));
+}
+
+#[test]
+fn split_extension_with_never_uses_a_leading_dot() {
+  let known = ["d.ts", "eslintrc.json"];
+  assert_eq!(split(".eslintrc", &known), None);
+  assert_eq!(split(".eslintrc.json", &known), Some((os(".eslintrc"), os("json"))));
+  assert_eq!(split(".d.ts", &known), Some((os(".d"), os("ts"))));
+  assert_eq!(split("..foo", &known), Some((os("."), os("foo"))));
+  assert_eq!(split("a/..", &known), None);
+  assert_eq!(split("/", &known), None);
+  assert_eq!(split("", &known), None);
+}
+
+#[test]
+fn full_extension_starts_at_the_first_extension_dot() {
+  assert_eq!("foo.d.ts".full_extension(), Some(os("d.ts")));
+  assert_eq!("dir.v2/archive.tar.gz".full_extension(), Some(os("tar.gz")));
+  assert_eq!("button.module.css".full_extension(), Some(os("module.css")));
+  assert_eq!(".eslintrc.json".full_extension(), Some(os("json")));
+  assert_eq!(".config.local.json".full_extension(), Some(os("local.json")));
+  assert_eq!(".eslintrc".full_extension(), None);
+  assert_eq!("foo.".full_extension(), Some(os("")));
+  assert_eq!("dir.v2/Makefile".full_extension(), None);
+  assert_eq!("..".full_extension(), None);
+}
+
+#[test]
+fn with_full_extension_replaces_adds_and_removes() {
+  assert_eq_str!("src/foo.d.ts".with_full_extension("js"), p!("src/foo.js"));
+  assert_eq_str!("archive.tar.gz".with_full_extension("zip"), "archive.zip");
+  assert_eq_str!("archive.tar.gz".with_full_extension(""), "archive");
+  assert_eq_str!("dir.v2/Makefile".with_full_extension("bak"), p!("dir.v2/Makefile.bak"));
+  assert_eq_str!(".eslintrc".with_full_extension("json"), ".eslintrc.json");
+  assert_eq_str!(".eslintrc.json".with_full_extension("yaml"), ".eslintrc.yaml");
+  assert_eq_str!("foo.".with_full_extension("txt"), "foo.txt");
+  assert_eq_str!("foo.d.ts".with_full_extension("d.mts"), "foo.d.mts");
+  assert_eq_str!("/".with_full_extension("js"), p!("/"));
+  assert_eq_str!("a/..".with_full_extension("js"), p!("a/.."));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_with_full_extension_keeps_text_after_the_file_name() {
+  assert_eq_str!("src/foo.d.ts/".with_full_extension("js"), "src/foo.js/");
+  assert_eq_str!("src/foo.d.ts/.".with_full_extension("js"), "src/foo.js/.");
+}
+
+#[test]
+#[should_panic(expected = "the extension contains a path separator")]
+fn with_full_extension_rejects_separators() {
+  let _ = "foo.ts".with_full_extension("a/b");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_extensions_work_on_non_utf8_names() {
+  use std::os::unix::ffi::OsStrExt;
+
+  let path = Path::new(OsStr::from_bytes(b"dir/\xFFname.d.ts"));
+  assert_eq!(path.full_extension(), Some(os("d.ts")));
+  assert_eq!(
+    path.split_extension_with(&["d.ts"]),
+    Some((OsStr::from_bytes(b"\xFFname"), os("d.ts")))
+  );
+  assert_eq!(
+    path.with_full_extension(OsStr::from_bytes(b"\xFE")).as_os_str(),
+    OsStr::from_bytes(b"dir/\xFFname.\xFE")
+  );
+
+  let known = [OsStr::from_bytes(b"x\xFF.gz")];
+  let path = Path::new(OsStr::from_bytes(b"a.x\xFF.gz"));
+  assert_eq!(path.split_extension_with(&known), Some((os("a"), known[0])));
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_known_extensions_ignore_ascii_case() {
+  assert_eq!(split(r"src\FOO.D.TS", &["d.ts"]), Some((os("FOO"), os("D.TS"))));
+  assert_eq_str!(r"C:\x\foo.d.ts".with_full_extension("js"), r"C:\x\foo.js");
+}
@@ -0,0 +1,174 @@
+use sugar_path::glob::{Pattern, PatternError};
+
+fn pattern(text: &str) -> Pattern {
+  Pattern::new(text).unwrap()
+}
+
+#[test]
+fn wildcards_match_within_one_component() {
+  let ts = pattern("src/*.ts");
+  assert!(ts.matches("src/index.ts"));
+  assert!(ts.matches("src/.hidden.ts"));
+  assert!(ts.matches("src/.ts"));
+  assert!(!ts.matches("src/nested/index.ts"));
+  assert!(!ts.matches("src/index.tsx"));
+
+  let question = pattern("a?c/*x*y*");
+  assert!(question.matches("abc/xy"));
+  assert!(question.matches("aéc/__x__y__"));
+  assert!(!question.matches("ac/xy"));
+  assert!(!question.matches("abc/yx"));
+}
+
+#[test]
+fn double_star_matches_any_depth() {
+  let any = pattern("src/**/*.rs");
+  assert!(any.matches("src/lib.rs"));
+  assert!(any.matches("src/a/b/c/lib.rs"));
+  assert!(!any.matches("tests/lib.rs"));
+
+  let everything = pattern("**");
+  assert!(everything.matches("a/b/c"));
+  assert!(everything.matches("."));
+  assert!(everything.matches("../a"));
+
+  assert!(pattern("**/node_modules/**").matches("a/node_modules/b/c.js"));
+  assert!(pattern("**/node_modules/**").matches("node_modules"));
+  assert!(pattern("a/**/b/**/c").matches("a/x/b/y/b/c"));
+  assert!(pattern("a/**/**/b").matches("a/b"));
+  assert!(pattern("a**b").matches("axyb"));
+  assert!(!pattern("a**b").matches("ax/yb"));
+}
+
+#[test]
+fn classes_match_one_character() {
+  let class = pattern("file[0-9][!a-c].[tj]s");
+  assert!(class.matches("file7z.ts"));
+  assert!(class.matches("file0d.js"));
+  assert!(!class.matches("file0a.js"));
+  assert!(!class.matches("filex1.ts"));
+
+  assert!(pattern("[]]").matches("]"));
+  assert!(pattern("[^-]x").matches("ax"));
+  assert!(!pattern("[^-]x").matches("-x"));
+  assert!(pattern("[a-]").matches("-"));
+  assert!(pattern(r"[\]]").matches("]"));
+}
+
+#[test]
+fn braces_expand_alternatives() {
+  let braces = pattern("{src,test/unit}/**/*.{ts,tsx}");
+  assert!(braces.matches("src/a.ts"));
+  assert!(braces.matches("test/unit/b/c.tsx"));
+  assert!(!braces.matches("test/d.ts"));
+
+  let nested = pattern("a.{j{s,sx},css}");
+  assert!(nested.matches("a.js"));
+  assert!(nested.matches("a.jsx"));
+  assert!(nested.matches("a.css"));
+  assert!(!nested.matches("a.j"));
+
+  assert!(pattern("x{,.min}.js").matches("x.js"));
+  assert!(pattern("x{,.min}.js").matches("x.min.js"));
+  assert!(pattern("a,b}").matches("a,b}"));
+}
+
+#[test]
+fn escapes_match_literally() {
+  assert!(pattern(r"\*.rs").matches("*.rs"));
+  assert!(!pattern(r"\*.rs").matches("a.rs"));
+  assert!(pattern(r"\{a,b\}").matches("{a,b}"));
+}
+
+#[test]
+fn paths_and_patterns_are_normalized() {
+  let plain = pattern("a/b");
+  assert!(plain.matches("a/./b"));
+  assert!(plain.matches("./a/b/"));
+  assert!(plain.matches("a/x/../b"));
+  assert!(pattern("./a//./b/").matches("a/b"));
+  assert!(pattern("a/x/../b").matches("a/b"));
+  assert!(pattern("../lib/*").matches("../lib/x"));
+  assert!(!pattern("*/x").matches("../x"));
+  assert!(pattern("").matches("."));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_rooted_patterns_and_case() {
+  assert!(pattern("/usr/**/*.h").matches("/usr/include/stdio.h"));
+  assert!(!pattern("/usr/**/*.h").matches("usr/include/stdio.h"));
+  assert!(!pattern("usr/**").matches("/usr/x"));
+  assert!(pattern("**/*.h").matches("/usr/include/stdio.h"));
+  assert!(pattern("/").matches("/"));
+
+  assert!(!pattern("*.RS").matches("lib.rs"));
+  assert!(pattern("*.RS").case_sensitive(false).matches("lib.rs"));
+  assert!(pattern("[A-C]*").case_sensitive(false).matches("bee"));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_non_utf8_components_match_on_bytes() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+  let path = Path::new(OsStr::from_bytes(b"dir/a\xFFb.rs"));
+  assert!(pattern("dir/*.rs").matches(path));
+  assert!(pattern("dir/a?b.rs").matches(path));
+  assert!(pattern("dir/a[!x]b.rs").matches(path));
+  assert!(!pattern("dir/a[x]b.rs").matches(path));
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_patterns_ignore_ascii_case_and_match_drives() {
+  assert!(pattern("SRC/*.RS").matches(r"src\lib.rs"));
+  assert!(!pattern("SRC/*.RS").case_sensitive(true).matches(r"src\lib.rs"));
+  assert!(pattern("C:/work/**/*.rs").matches(r"c:\Work\src\lib.rs"));
+  assert!(!pattern("C:/work/**").matches(r"D:\work\x"));
+  assert!(pattern("/work/*").matches(r"\work\x"));
+}
+
+#[test]
+fn malformed_patterns_are_rejected() {
+  assert_eq!(Pattern::new("a/[bc").unwrap_err(), PatternError::UnclosedClass { position: 2 });
+  assert_eq!(Pattern::new("{a,{b}").unwrap_err(), PatternError::UnclosedBrace { position: 0 });
+  assert_eq!(Pattern::new("x[z-a]").unwrap_err(), PatternError::InvalidRange { position: 2 });
+  assert_eq!(Pattern::new("a\\").unwrap_err(), PatternError::DanglingEscape);
+  assert_eq!(
+    PatternError::UnclosedBrace { position: 3 }.to_string(),
+    "`{` at byte 3 has no closing `}`"
+  );
+}
+
+#[test]
+fn deep_brace_chains_are_bounded() {
+  let ten = "{a,b}".repeat(10);
+  let compiled = pattern(&ten);
+  assert!(compiled.matches("abababbbaa"));
+  assert!(!compiled.matches("abababbbac"));
+  assert!(pattern(&"{a,a}".repeat(10)).matches("aaaaaaaaaa"));
+
+  // Eleven groups expand to 2,048 alternatives. A chain this deep used to be
+  // expanded eagerly and stall compilation for minutes.
+  for depth in [11, 18, 64, 1000] {
+    assert_eq!(
+      Pattern::new(&"{a,b}".repeat(depth)).unwrap_err(),
+      PatternError::TooManyAlternatives { limit: 1024 },
+      "{depth} groups"
+    );
+  }
+  let nested = format!("{}x{}", "{a,".repeat(500), "}".repeat(500));
+  assert!(pattern(&nested).matches("a"));
+  assert_eq!(
+    PatternError::TooManyAlternatives { limit: 1024 }.to_string(),
+    "the braces expand to more than 1024 alternatives"
+  );
+}
+
+#[test]
+fn pattern_keeps_its_source() {
+  let compiled = pattern("src/**/*.rs");
+  assert_eq!(compiled.as_str(), "src/**/*.rs");
+  assert_eq!(compiled.to_string(), "src/**/*.rs");
+}
@@ -0,0 +1,155 @@
+use std::{
+  borrow::Cow,
+  path::{Path, PathBuf},
+};
+
+use sugar_path::SugarPath;
+mod test_utils;
+
+fn assert_borrows_from(receiver: &Path, result: &Path) {
+  let receiver_bytes = receiver.as_os_str().as_encoded_bytes();
+  let result_bytes = result.as_os_str().as_encoded_bytes();
+  let receiver_start = receiver_bytes.as_ptr() as usize;
+  let result_start = result_bytes.as_ptr() as usize;
+  assert!(
+    result_start >= receiver_start
+      && result_start + result_bytes.len() <= receiver_start + receiver_bytes.len(),
+    "{result:?} does not borrow from {receiver:?}",
+  );
+}
+
+fn ancestors(path: &str) -> Vec<PathBuf> {
+  path.lexical_ancestors().map(Cow::into_owned).collect()
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_lexical_parent_resolves_components_first() {
+  assert_eq_str!(p!("a/b/c").lexical_parent().unwrap(), "a/b");
+  assert_eq_str!(p!("a/b/").lexical_parent().unwrap(), "a");
+  assert_eq_str!(p!("a").lexical_parent().unwrap(), ".");
+  assert_eq_str!(p!("a/..").lexical_parent().unwrap(), "..");
+  assert_eq_str!(p!(".").lexical_parent().unwrap(), "..");
+  assert_eq_str!(p!("").lexical_parent().unwrap(), "..");
+  assert_eq_str!(p!("./").lexical_parent().unwrap(), "..");
+  assert_eq_str!(p!("..").lexical_parent().unwrap(), "../..");
+  assert_eq_str!(p!("../a/./b/..").lexical_parent().unwrap(), "..");
+  assert_eq_str!(p!("/a").lexical_parent().unwrap(), "/");
+  assert_eq_str!(p!("/a/b/../c").lexical_parent().unwrap(), "/a");
+  assert_eq!(p!("/").lexical_parent(), None);
+  assert_eq!(p!("/..").lexical_parent(), None);
+  assert_eq!(p!("//").lexical_parent(), None);
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_lexical_parent_stops_at_prefixes() {
+  assert_eq_str!(p!(r"C:\a\b").lexical_parent().unwrap(), r"C:\a");
+  assert_eq_str!(p!(r"c:\a").lexical_parent().unwrap(), r"c:\");
+  assert_eq_str!(p!("C:/a/../b/c").lexical_parent().unwrap(), r"C:\b");
+  assert_eq_str!(p!("C:a").lexical_parent().unwrap(), "C:.");
+  assert_eq_str!(p!("C:").lexical_parent().unwrap(), "C:..");
+  assert_eq_str!(p!(r"C:..\a\..").lexical_parent().unwrap(), r"C:..\..");
+  assert_eq_str!(p!(r"\\server\share\a").lexical_parent().unwrap(), r"\\server\share\");
+  assert_eq_str!(p!(r"\\?\C:\a\b").lexical_parent().unwrap(), r"\\?\C:\a");
+  assert_eq_str!(p!(r".\C:foo\bar").lexical_parent().unwrap(), r".\C:foo");
+  assert_eq_str!(p!(r"a\..").lexical_parent().unwrap(), "..");
+  assert_eq_str!(p!("..").lexical_parent().unwrap(), r"..\..");
+  assert_eq!(p!(r"C:\").lexical_parent(), None);
+  assert_eq!(p!(r"C:\..").lexical_parent(), None);
+  assert_eq!(p!(r"\").lexical_parent(), None);
+  assert_eq!(p!(r"\\server\share").lexical_parent(), None);
+  assert_eq!(p!(r"\\?\UNC\server\share\").lexical_parent(), None);
+}
+
+#[test]
+fn lexical_parent_borrows_prefixes_of_normalized_receivers() {
+  #[cfg(target_family = "unix")]
+  let cases = [("/workspace/src/lib.rs", "/workspace/src"), ("src/", "."), ("../a/b", "../a")];
+  #[cfg(target_family = "windows")]
+  let cases =
+    [(r"C:\workspace\src\lib.rs", r"C:\workspace\src"), (r"src\", "."), (r"..\a\b", r"..\a")];
+
+  for (input, expected) in cases {
+    let receiver = Path::new(input);
+    let Some(Cow::Borrowed(parent)) = receiver.lexical_parent() else {
+      panic!("expected the parent of {input:?} to borrow");
+    };
+    assert_eq!(parent.as_os_str(), Path::new(expected).as_os_str());
+    if expected != "." {
+      assert_borrows_from(receiver, parent);
+    }
+  }
+
+  #[cfg(target_family = "unix")]
+  let owned = ["a/./b", "a/..", ".."];
+  #[cfg(target_family = "windows")]
+  let owned = [r"a\.\b", r"a\..", "..", "C:a"];
+  for input in owned {
+    assert!(
+      matches!(Path::new(input).lexical_parent(), Some(Cow::Owned(_))),
+      "expected the parent of {input:?} to be owned",
+    );
+  }
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_lexical_ancestors_are_finite_and_normalized() {
+  assert_eq!(ancestors("/a/b/c"), [p!("/a/b/c"), p!("/a/b"), p!("/a"), p!("/")]);
+  assert_eq!(ancestors("/a/./b/../c/"), [p!("/a/c/"), p!("/a"), p!("/")]);
+  assert_eq!(ancestors("a/b"), [p!("a/b"), p!("a"), p!(".")]);
+  assert_eq!(ancestors("../a"), [p!("../a"), p!("..")]);
+  assert_eq!(ancestors("a/../.."), [p!("..")]);
+  assert_eq!(ancestors(""), [p!(".")]);
+  assert_eq!(ancestors("/"), [p!("/")]);
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_lexical_ancestors_stop_at_roots_and_prefixes() {
+  assert_eq!(ancestors(r"C:\a\b"), [p!(r"C:\a\b"), p!(r"C:\a"), p!(r"C:\")]);
+  assert_eq!(ancestors("C:a/b"), [p!(r"C:a\b"), p!("C:a"), p!("C:.")]);
+  assert_eq!(
+    ancestors(r"\\server\share\a\b"),
+    [p!(r"\\server\share\a\b"), p!(r"\\server\share\a"), p!(r"\\server\share\")]
+  );
+  assert_eq!(ancestors(r"..\a"), [p!(r"..\a"), p!("..")]);
+}
+
+#[test]
+fn lexical_ancestors_borrow_from_normalized_receivers() {
+  #[cfg(target_family = "unix")]
+  let input = "/workspace/rolldown/src/lib.rs";
+  #[cfg(target_family = "windows")]
+  let input = r"C:\workspace\rolldown\src\lib.rs";
+
+  let receiver = Path::new(input);
+  let mut count = 0;
+  for ancestor in receiver.lexical_ancestors() {
+    let Cow::Borrowed(ancestor) = ancestor else {
+      panic!("expected every ancestor of {input:?} to borrow");
+    };
+    assert_borrows_from(receiver, ancestor);
+    count += 1;
+  }
+  assert_eq!(count, 5);
+
+  let mut iter = receiver.lexical_ancestors();
+  assert!(iter.by_ref().last().is_some());
+  assert!(iter.next().is_none());
+}
+
+#[test]
+fn lexical_parent_matches_the_first_ancestor_step() {
+  #[cfg(target_family = "unix")]
+  let cases = ["a/b/c", "/a/b", "a", "./a/../b/", "/"];
+  #[cfg(target_family = "windows")]
+  let cases = [r"a\b\c", r"C:\a\b", "a", r".\a\..\b\", r"C:\", "C:a"];
+
+  for input in cases {
+    let parent = input.lexical_parent().map(Cow::into_owned);
+    let second = input.lexical_ancestors().nth(1).map(Cow::into_owned);
+    assert_eq!(parent, second, "input {input:?}");
+  }
+}
@@ -0,0 +1,39 @@
+use std::{
+  borrow::Cow,
+  ffi::{OsStr, OsString},
+  path::Path,
+};
+
+use sugar_path::SugarPath;
+mod test_utils;
+
+#[test]
+fn os_str_receivers_match_path_receivers() {
+  let input = OsString::from(pb!("src", ".", "lib", "..", "main.rs"));
+  assert_eq!(input.normalize(), Path::new(&input).normalize());
+  assert_eq!(input.as_os_str().to_slash(), "src/./lib/../main.rs");
+  assert_eq!(input.as_path(), Path::new(&input));
+
+  let cwd = std::env::current_dir().unwrap();
+  assert_eq!(input.absolutize_with(&cwd), cwd.join(pb!("src", "main.rs")));
+  assert_eq!(OsStr::new("a.d.ts").full_extension(), Some(OsStr::new("d.ts")));
+}
+
+#[test]
+fn os_str_receivers_borrow_clean_input() {
+  let input = OsStr::new("a");
+  let Cow::Borrowed(normalized) = input.normalize() else {
+    panic!("expected a borrowed result");
+  };
+  assert_eq!(normalized.as_os_str().as_encoded_bytes().as_ptr(), input.as_encoded_bytes().as_ptr());
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_non_utf8_os_str_receivers_keep_their_bytes() {
+  use std::os::unix::ffi::OsStrExt;
+
+  let input = OsStr::from_bytes(b"dir/./\xFF");
+  assert_eq!(input.normalize().as_os_str().as_bytes(), b"dir/\xFF");
+  assert_eq!(input.try_to_slash(), None);
+}
@@ -174,3 +174,85 @@ fn owned_apis_preserve_and_replace_invalid_windows_encoding() {
   let no_native_separator = PathBuf::from(OsString::from_wide(&no_native_separator_wide));
   assert_eq!(no_native_separator.into_slash_lossy(), "invalid-\u{fffd}/tail");
 }
+
+#[test]
+fn into_absolutized_with_writes_cwd_into_clean_relative_buffers() {
+  let cwd = std::env::current_dir().unwrap();
+  #[cfg(target_family = "unix")]
+  let cases = ["src/lib.rs", "../chunks/shared.js", ""];
+  #[cfg(target_family = "windows")]
+  let cases = [r"src\lib.rs", r"..\chunks\shared.js", ""];
+
+  for input in cases {
+    let path = owned_path_with_capacity(input);
+    let identity = buffer_identity(&path);
+    let expected = Path::new(input).absolutize_with(&cwd).into_owned();
+    let absolutized = path.into_absolutized_with(&cwd);
+
+    assert_eq!(absolutized.as_os_str(), expected.as_os_str(), "input {input:?}");
+    if !input.starts_with("..") {
+      assert_eq!(buffer_identity(&absolutized), identity, "input {input:?}");
+    }
+  }
+}
+
+#[test]
+fn consuming_absolutize_matches_borrowed_api() {
+  let cwd = std::env::current_dir().unwrap();
+  #[cfg(target_family = "unix")]
+  let cases = ["foo/./bar/../baz/", "/abs/./x/..", "/", ".", "a//b"];
+  #[cfg(target_family = "windows")]
+  let cases = [r"foo\.\bar\..\baz\", r"C:\abs\.\x\..", r"\rooted\a", r"\\?\C:\a\.", "a//b"];
+
+  for input in cases {
+    // An exact-capacity buffer cannot hold the cwd and takes the copying path.
+    for path in [PathBuf::from(input), owned_path_with_capacity(input)] {
+      let expected = Path::new(input).absolutize_with(&cwd).into_owned();
+      assert_eq!(path.clone().into_absolutized_with(&cwd), expected, "input {input:?}");
+      assert_eq!(path.clone().try_into_absolutized().unwrap(), expected, "input {input:?}");
+      assert_eq!(path.into_absolutized(), expected, "input {input:?}");
+    }
+  }
+}
+
+#[test]
+#[should_panic(expected = "explicit current directory must be absolute")]
+fn into_absolutized_with_rejects_relative_cwd() {
+  let _ = PathBuf::from("src").into_absolutized_with("relative");
+}
+
+#[test]
+fn into_relative_with_moves_borrowed_suffixes_within_the_receiver() {
+  let cwd = std::env::current_dir().unwrap();
+  let base = cwd.join("workspace");
+  let target = base.join("src").join("lib.rs");
+  let mut path = PathBuf::with_capacity(256);
+  path.push(&target);
+  let identity = buffer_identity(&path);
+
+  let relative = path.into_relative_with(&base, &cwd);
+  assert_eq!(relative, Path::new("src").join("lib.rs"));
+  assert_eq!(buffer_identity(&relative), identity);
+}
+
+#[test]
+fn into_relative_with_matches_borrowed_api() {
+  let cwd = std::env::current_dir().unwrap();
+  let cases = [
+    (cwd.join("a").join("b"), cwd.join("a").join("c")),
+    (cwd.join("a"), cwd.join("a")),
+    (cwd.join("a").join(".").join("b"), cwd.join("a")),
+    (PathBuf::from("x").join("y"), PathBuf::from("x")),
+    (PathBuf::from("x"), cwd.join("z")),
+    (PathBuf::from(".."), PathBuf::from("a")),
+  ];
+
+  for (target, base) in cases {
+    let expected = target.relative_with(&base, &cwd).into_owned();
+    assert_eq!(
+      target.clone().into_relative_with(&base, &cwd),
+      expected,
+      "{target:?} from {base:?}"
+    );
+  }
+}
@@ -0,0 +1,97 @@
+use std::{borrow::Cow, path::Path};
+
+use sugar_path::PathRemapper;
+mod test_utils;
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_remapper_selects_the_longest_component_prefix() {
+  let remapper: PathRemapper = [
+    ("/home/ci/work", "/src"),
+    ("/home/ci/work/vendor/", "/vendor"),
+    ("/home/ci/.cargo/registry/./src/../src", "/cargo"),
+  ]
+  .into_iter()
+  .collect();
+
+  assert_eq_str!(remapper.remap("/home/ci/work/app/main.rs"), "/src/app/main.rs");
+  assert_eq_str!(remapper.remap("/home/ci/work/vendor/lib.rs"), "/vendor/lib.rs");
+  assert_eq_str!(remapper.remap("/home/ci/work/vendored/lib.rs"), "/src/vendored/lib.rs");
+  assert_eq_str!(remapper.remap("/home/ci/work"), "/src");
+  assert_eq_str!(remapper.remap("/home/ci/work/"), "/src");
+  assert_eq_str!(
+    remapper.remap("/home/ci/.cargo/registry/src/serde/lib.rs"),
+    "/cargo/serde/lib.rs"
+  );
+  assert_eq_str!(remapper.remap("/home/ci/work/./app/../vendor/x.rs"), "/vendor/x.rs");
+  assert_eq_str!(remapper.remap("/home/ci/workspace/main.rs"), "/home/ci/workspace/main.rs");
+  assert_eq_str!(remapper.remap("/home/ci"), "/home/ci");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_remapper_matches_relative_prefixes_only_against_relative_inputs() {
+  let remapper: PathRemapper = [("src", "crate"), ("..", "parent")].into_iter().collect();
+
+  assert_eq_str!(remapper.remap("src/lib.rs"), "crate/lib.rs");
+  assert_eq_str!(remapper.remap("./src/lib.rs"), "crate/lib.rs");
+  assert_eq_str!(remapper.remap("../lib.rs"), "parent/lib.rs");
+  assert_eq_str!(remapper.remap("/src/lib.rs"), "/src/lib.rs");
+
+  let current: PathRemapper = [(".", "root")].into_iter().collect();
+  assert_eq_str!(current.remap("a/b"), "root/a/b");
+  assert_eq_str!(current.remap(""), "root");
+  assert_eq_str!(current.remap("../a"), "../a");
+  assert_eq_str!(current.remap("/a"), "/a");
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_remapper_ignores_ascii_case_and_keeps_root_kinds_distinct() {
+  let remapper: PathRemapper = [
+    (r"C:\ci\work", r"\src"),
+    (r"\\server\share\ci", r"\share"),
+    (r"\\?\C:\ci\work", r"\verbatim"),
+  ]
+  .into_iter()
+  .collect();
+
+  assert_eq_str!(remapper.remap(r"c:\CI\Work\main.rs"), r"\src\main.rs");
+  assert_eq_str!(remapper.remap("C:/ci/work/app/main.rs"), r"\src\app\main.rs");
+  assert_eq_str!(remapper.remap(r"\\SERVER\Share\ci\lib.rs"), r"\share\lib.rs");
+  assert_eq_str!(remapper.remap(r"\\?\c:\ci\work\lib.rs"), r"\verbatim\lib.rs");
+  assert_eq_str!(remapper.remap(r"D:\ci\work\main.rs"), r"D:\ci\work\main.rs");
+  assert_eq_str!(remapper.remap(r"C:\ci\workspace\main.rs"), r"C:\ci\workspace\main.rs");
+  assert_eq_str!(remapper.remap(r"C:ci\work\main.rs"), r"C:ci\work\main.rs");
+
+  let prefix_like: PathRemapper = [(r"C:\ci", "out")].into_iter().collect();
+  assert_eq_str!(prefix_like.remap(r"C:\ci\D:data\x"), r"out\D:data\x");
+}
+
+#[test]
+fn later_mappings_win_for_equal_prefixes() {
+  let mut remapper = PathRemapper::new();
+  assert!(remapper.is_empty());
+  remapper.insert("workspace", "first");
+  remapper.insert("workspace/./", "second");
+  assert_eq!(remapper.len(), 2);
+
+  assert_eq!(remapper.remap("workspace/lib.rs"), Path::new("second").join("lib.rs"));
+}
+
+#[test]
+fn unmatched_inputs_borrow_the_receiver_unchanged() {
+  let remapper: PathRemapper = [("workspace", "src")].into_iter().collect();
+
+  for input in ["other/./lib.rs", "workspaces/lib.rs", ""] {
+    let remapped = remapper.remap(input);
+    let Cow::Borrowed(remapped) = remapped else {
+      panic!("expected unmatched input {input:?} to borrow");
+    };
+    assert_eq!(remapped.as_os_str(), Path::new(input).as_os_str());
+    assert_eq!(remapped.as_os_str().as_encoded_bytes().as_ptr(), input.as_ptr());
+  }
+
+  assert!(matches!(PathRemapper::new().remap("workspace"), Cow::Borrowed(_)));
+  assert!(matches!(remapper.remap("workspace/lib.rs"), Cow::Owned(_)));
+}
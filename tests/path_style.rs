@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use sugar_path::{PathStyle, TranslateError, translate_path};
+
+use PathStyle::{Cygwin, Msys, Windows, Wsl};
+
+fn translate(path: &str, from: PathStyle, to: PathStyle) -> Result<String, TranslateError> {
+  translate_path(path, from, to).map(Cow::into_owned)
+}
+
+#[test]
+fn drive_paths_translate_between_every_style() {
+  let spellings = [
+    (Windows, r"C:\Users\x"),
+    (Wsl, "/mnt/C/Users/x"),
+    (Msys, "/C/Users/x"),
+    (Cygwin, "/cygdrive/C/Users/x"),
+  ];
+  for (from, input) in spellings {
+    for (to, expected) in spellings {
+      assert_eq!(translate(input, from, to).as_deref(), Ok(expected), "{from:?} -> {to:?}");
+    }
+  }
+}
+
+#[test]
+fn drive_roots_and_separators_are_translated_exactly() {
+  assert_eq!(translate("/mnt/c", Wsl, Windows).as_deref(), Ok(r"c:\"));
+  assert_eq!(translate("/mnt/c/", Wsl, Windows).as_deref(), Ok(r"c:\"));
+  assert_eq!(translate("/c/a/", Msys, Windows).as_deref(), Ok(r"c:\a\"));
+  assert_eq!(translate(r"d:\", Windows, Wsl).as_deref(), Ok("/mnt/d"));
+  assert_eq!(translate("D:/a/b", Windows, Cygwin).as_deref(), Ok("/cygdrive/D/a/b"));
+  assert_eq!(translate(r"C:\\a\..\b", Windows, Msys).as_deref(), Ok("/C/a/../b"));
+  assert_eq!(translate("/cygdrive/z/a\\b", Cygwin, Wsl).as_deref(), Ok("/mnt/z/a\\b"));
+}
+
+#[test]
+fn relative_paths_only_convert_separators_and_borrow_when_unchanged() {
+  assert_eq!(translate(r"src\lib.rs", Windows, Wsl).as_deref(), Ok("src/lib.rs"));
+  assert_eq!(translate("../src/lib.rs", Msys, Windows).as_deref(), Ok(r"..\src\lib.rs"));
+
+  for (input, from, to) in [
+    ("src/lib.rs", Wsl, Msys),
+    ("lib.rs", Windows, Cygwin),
+    ("src/lib.rs", Windows, Wsl),
+    (r"C:\x", Windows, Windows),
+    ("/usr/bin", Wsl, Wsl),
+  ] {
+    let translated = translate_path(input, from, to).unwrap();
+    assert!(matches!(translated, Cow::Borrowed(_)), "{input:?} {from:?} -> {to:?}");
+    assert_eq!(translated, input);
+  }
+}
+
+#[test]
+fn unc_paths_map_only_where_the_target_has_a_unc_spelling() {
+  assert_eq!(translate(r"\\server\share\x", Windows, Msys).as_deref(), Ok("//server/share/x"));
+  assert_eq!(translate("//server/share/x", Cygwin, Windows).as_deref(), Ok(r"\\server\share\x"));
+  assert_eq!(translate("//server/share/x", Msys, Cygwin).as_deref(), Ok("//server/share/x"));
+  assert_eq!(translate(r"\\server\share\x", Windows, Wsl), Err(TranslateError::Unc));
+  assert_eq!(translate("//server/share", Msys, Wsl), Err(TranslateError::Unc));
+}
+
+#[test]
+fn paths_without_a_mapping_are_errors() {
+  assert_eq!(translate("C:x", Windows, Wsl), Err(TranslateError::DriveRelative));
+  assert_eq!(translate("C:", Windows, Msys), Err(TranslateError::DriveRelative));
+  assert_eq!(translate(r"\Users\x", Windows, Cygwin), Err(TranslateError::DriveRelative));
+  assert_eq!(translate(r"\\?\C:\x", Windows, Wsl), Err(TranslateError::Namespace));
+  assert_eq!(translate(r"\\.\COM1", Windows, Msys), Err(TranslateError::Namespace));
+  assert_eq!(translate("/usr/bin", Wsl, Windows), Err(TranslateError::NotMounted));
+  assert_eq!(translate("/mnt/cd/x", Wsl, Windows), Err(TranslateError::NotMounted));
+  assert_eq!(translate("/mnt/c/x", Msys, Windows), Err(TranslateError::NotMounted));
+  assert_eq!(translate("/c/x", Cygwin, Windows), Err(TranslateError::NotMounted));
+  assert_eq!(translate("//mnt/c/x", Wsl, Windows), Err(TranslateError::NotMounted));
+  assert_eq!(translate("///c/x", Msys, Windows), Err(TranslateError::NotMounted));
+
+  assert_eq!(
+    TranslateError::Unc.to_string(),
+    "the UNC path has no mapping in the target path style"
+  );
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use sugar_path::PathTrie;
+
+fn descendants<'a>(trie: &'a PathTrie<&'static str>, prefix: &str) -> Vec<&'a str> {
+  trie.descendants(prefix).map(|(_, value)| *value).collect()
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_trie_finds_the_deepest_owner_on_component_boundaries() {
+  let trie: PathTrie<_> = [
+    ("/repo", "root"),
+    ("/repo/packages/app/", "app"),
+    ("/repo/packages/app/vendor/./lib", "lib"),
+    ("/repo/packages/core", "core"),
+  ]
+  .into_iter()
+  .collect();
+
+  assert_eq!(trie.len(), 4);
+  assert_eq!(
+    trie.longest_prefix("/repo/packages/app/src/main.rs"),
+    Some((Path::new("/repo/packages/app"), &"app"))
+  );
+  assert_eq!(
+    trie.longest_prefix("/repo/packages/app/src/../vendor/lib/index.js"),
+    Some((Path::new("/repo/packages/app/vendor/lib"), &"lib"))
+  );
+  assert_eq!(
+    trie.longest_prefix("/repo/packages/application/x"),
+    Some((Path::new("/repo"), &"root"))
+  );
+  assert_eq!(
+    trie.longest_prefix("/repo/packages/app"),
+    Some((Path::new("/repo/packages/app"), &"app"))
+  );
+  assert_eq!(trie.longest_prefix("/repository"), None);
+  assert_eq!(trie.longest_prefix("/"), None);
+  assert_eq!(trie.longest_prefix("repo/packages/app"), None);
+
+  assert_eq!(trie.get("/repo/packages/./core/"), Some(&"core"));
+  assert_eq!(trie.get("/repo/packages"), None);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_relative_keys_are_grouped_by_leading_parents() {
+  let trie: PathTrie<_> =
+    [(".", "cwd"), ("..", "parent"), ("../a", "sibling"), ("/", "root")].into_iter().collect();
+
+  assert_eq!(trie.longest_prefix("a/b").map(|(_, value)| *value), Some("cwd"));
+  assert_eq!(trie.longest_prefix("").map(|(_, value)| *value), Some("cwd"));
+  assert_eq!(trie.longest_prefix("../b").map(|(_, value)| *value), Some("parent"));
+  assert_eq!(trie.longest_prefix("x/../../a/b").map(|(_, value)| *value), Some("sibling"));
+  assert_eq!(trie.longest_prefix("../..").map(|(_, value)| *value), None);
+  assert_eq!(trie.longest_prefix("/a").map(|(_, value)| *value), Some("root"));
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_trie_ignores_ascii_case_and_keeps_root_kinds_distinct() {
+  let trie: PathTrie<_> = [
+    (r"C:\repo", "drive"),
+    (r"\\server\share\repo", "unc"),
+    (r"\\?\C:\repo", "verbatim"),
+    ("C:repo", "drive-relative"),
+  ]
+  .into_iter()
+  .collect();
+
+  assert_eq!(trie.longest_prefix(r"c:\REPO\src").map(|(_, value)| *value), Some("drive"));
+  assert_eq!(trie.longest_prefix("C:/repo/src").map(|(_, value)| *value), Some("drive"));
+  assert_eq!(trie.longest_prefix(r"//SERVER/share/Repo/x").map(|(_, value)| *value), Some("unc"));
+  assert_eq!(trie.longest_prefix(r"\\?\c:\repo\x").map(|(_, value)| *value), Some("verbatim"));
+  assert_eq!(trie.longest_prefix(r"c:Repo\x").map(|(_, value)| *value), Some("drive-relative"));
+  assert_eq!(trie.longest_prefix(r"D:\repo\x"), None);
+  assert_eq!(trie.longest_prefix(r"\repo\x"), None);
+
+  let mut trie = trie;
+  assert_eq!(trie.insert(r"c:\REPO", "replaced"), Some("drive"));
+  assert_eq!(trie.longest_prefix(r"C:\repo\x"), Some((Path::new(r"C:\repo"), &"replaced")));
+}
+
+#[test]
+fn insert_replaces_equal_keys_and_keeps_the_first_spelling() {
+  let mut trie = PathTrie::new();
+  assert!(trie.is_empty());
+  assert_eq!(trie.insert("workspace/./app/", 1), None);
+  assert_eq!(trie.insert("workspace/app", 2), Some(1));
+  assert_eq!(trie.len(), 1);
+
+  let (key, value) = trie.longest_prefix("workspace/app/src").unwrap();
+  assert_eq!(key, Path::new("workspace/app"));
+  assert_eq!(*value, 2);
+}
+
+#[test]
+fn descendants_include_the_prefix_and_visit_parents_first() {
+  let trie: PathTrie<_> = [
+    ("a/c", "a/c"),
+    ("a", "a"),
+    ("a/b/d", "a/b/d"),
+    ("a/b", "a/b"),
+    ("ab", "ab"),
+    ("../a", "../a"),
+  ]
+  .into_iter()
+  .collect();
+
+  assert_eq!(descendants(&trie, "a"), ["a", "a/b", "a/b/d", "a/c"]);
+  assert_eq!(descendants(&trie, "a/./b/"), ["a/b", "a/b/d"]);
+  assert_eq!(descendants(&trie, "."), ["a", "a/b", "a/b/d", "a/c", "ab"]);
+  assert_eq!(descendants(&trie, ".."), ["../a"]);
+  assert!(descendants(&trie, "a/x").is_empty());
+
+  let mut iter = trie.descendants("a/b/d");
+  assert_eq!(iter.next().map(|(key, _)| key), Some(Path::new("a/b/d")));
+  assert!(iter.next().is_none());
+  assert!(iter.next().is_none());
+}
@@ -0,0 +1,114 @@
+use std::ffi::OsString;
+
+use sugar_path::{PortabilityIssue, PortabilityIssueKind, PortabilityTargets, SugarPath};
+
+use PortabilityIssueKind::{
+  IllegalCharacter, LeadingHyphen, ReservedName, TooLong, TrailingDotOrSpace,
+};
+
+const WINDOWS: PortabilityTargets = PortabilityTargets::WINDOWS;
+const MACOS: PortabilityTargets = PortabilityTargets::MACOS;
+const POSIX: PortabilityTargets = PortabilityTargets::POSIX;
+
+fn kinds(path: &str, targets: PortabilityTargets) -> Vec<(usize, PortabilityIssueKind)> {
+  path
+    .portability_issues(targets)
+    .into_iter()
+    .map(|issue| (issue.component_index, issue.kind))
+    .collect()
+}
+
+#[test]
+fn windows_rejects_reserved_names_illegal_characters_and_trailing_dots() {
+  assert_eq!(kinds("dist/CON", WINDOWS), [(1, ReservedName)]);
+  assert_eq!(kinds("dist/nul.txt", WINDOWS), [(1, ReservedName)]);
+  assert_eq!(kinds("dist/com1.tar.gz", WINDOWS), [(1, ReservedName)]);
+  assert_eq!(kinds("dist/LPT9 .log", WINDOWS), [(1, ReservedName)]);
+  assert_eq!(kinds("dist/COM²", WINDOWS), [(1, ReservedName)]);
+  assert_eq!(kinds("dist/name.", WINDOWS), [(1, TrailingDotOrSpace)]);
+  assert_eq!(kinds("dist/name ", WINDOWS), [(1, TrailingDotOrSpace)]);
+  assert_eq!(
+    kinds("a<b>|c", WINDOWS),
+    [(0, IllegalCharacter('<')), (0, IllegalCharacter('>')), (0, IllegalCharacter('|')),]
+  );
+  assert_eq!(
+    kinds("what?/*.js", WINDOWS),
+    [(0, IllegalCharacter('?')), (1, IllegalCharacter('*'))]
+  );
+  assert_eq!(kinds("tab\there", WINDOWS), [(0, IllegalCharacter('\t'))]);
+  assert_eq!(kinds("a\"b\"", WINDOWS), [(0, IllegalCharacter('"'))]);
+
+  for portable in ["console", "com10", "nullable", "CON1", "x.CON", "dist/index.js", ".hidden"] {
+    assert!(portable.portability_issues(WINDOWS).is_empty(), "{portable:?}");
+  }
+}
+
+#[test]
+fn macos_and_posix_apply_their_own_character_rules() {
+  assert_eq!(kinds("a:b", MACOS), [(0, IllegalCharacter(':'))]);
+  assert!("CON/a<b>".portability_issues(MACOS).is_empty());
+
+  assert_eq!(kinds("-flag", POSIX), [(0, LeadingHyphen)]);
+  assert_eq!(kinds("a b/é", POSIX), [(0, IllegalCharacter(' ')), (1, IllegalCharacter('é'))]);
+  assert!("Makefile/v1.2_final-3".portability_issues(POSIX).is_empty());
+}
+
+#[test]
+fn issues_record_the_component_and_every_rejecting_target() {
+  let issues = "assets/a:b.png".portability_issues(PortabilityTargets::ALL);
+  assert_eq!(
+    issues,
+    [PortabilityIssue {
+      component_index: 1,
+      component: OsString::from("a:b.png"),
+      kind: IllegalCharacter(':'),
+      targets: WINDOWS | MACOS | POSIX,
+    }]
+  );
+
+  let issues = "a:b".portability_issues(WINDOWS | POSIX);
+  assert_eq!(issues[0].targets, WINDOWS | POSIX);
+  assert!("a:b".portability_issues(PortabilityTargets::empty()).is_empty());
+}
+
+#[test]
+fn length_limits_count_the_target_encoding() {
+  let ascii = "a".repeat(256);
+  assert_eq!(kinds(&ascii, WINDOWS | MACOS), [(0, TooLong)]);
+  assert!("a".repeat(255).portability_issues(PortabilityTargets::ALL).is_empty());
+
+  // 128 two-byte characters: 256 UTF-8 bytes but 128 UTF-16 units.
+  let accented = "é".repeat(128);
+  let issues = accented.portability_issues(WINDOWS | MACOS);
+  assert_eq!(issues.len(), 1);
+  assert_eq!((issues[0].kind, issues[0].targets), (TooLong, MACOS));
+}
+
+#[test]
+fn only_normal_components_are_checked() {
+  #[cfg(target_family = "unix")]
+  let (path, index) = ("/./../aux", 2);
+  #[cfg(target_family = "windows")]
+  let (path, index) = (r"C:\.\..\aux", 3);
+
+  assert_eq!(kinds(path, WINDOWS), [(index, ReservedName)]);
+  assert!("".portability_issues(PortabilityTargets::ALL).is_empty());
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_components_are_checked_for_other_hosts() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+  assert_eq!(kinds(r"src\lib.rs", WINDOWS), [(0, IllegalCharacter('\\'))]);
+  assert_eq!(kinds("C:foo", WINDOWS), [(0, IllegalCharacter(':'))]);
+
+  let issues =
+    Path::new(OsStr::from_bytes(b"dir/na\xffme")).portability_issues(PortabilityTargets::ALL);
+  assert_eq!(issues.len(), 1);
+  assert_eq!(issues[0].component_index, 1);
+  assert_eq!(
+    (issues[0].kind, issues[0].targets),
+    (PortabilityIssueKind::NotUnicode, PortabilityTargets::ALL)
+  );
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "rayon")]
+
+use std::{
+  borrow::Cow,
+  env,
+  path::{Path, PathBuf},
+  sync::{Arc, Barrier},
+  thread,
+};
+
+use sugar_path::{
+  SugarPath,
+  rayon::{normalize_all, relative_all, to_slash_all},
+};
+
+fn module_graph() -> Vec<PathBuf> {
+  #[cfg(target_family = "windows")]
+  let root = Path::new(r"C:\workspace");
+  #[cfg(not(target_family = "windows"))]
+  let root = Path::new("/workspace");
+
+  (0..512)
+    .map(|index| match index % 4 {
+      0 => root.join("src").join(format!("{index}.js")),
+      1 => root.join("src").join(".").join("nested").join("..").join(format!("{index}.js")),
+      2 => root.join("node_modules").join(format!("pkg{index}")).join("index.js"),
+      _ => PathBuf::from("..").join("outside").join(format!("{index}.js")),
+    })
+    .collect()
+}
+
+#[test]
+fn batch_results_match_per_element_results_in_order() {
+  let graph = module_graph();
+
+  let normalized = normalize_all(&graph);
+  assert_eq!(normalized.len(), graph.len());
+  for (path, result) in graph.iter().zip(&normalized) {
+    let expected = path.normalize();
+    assert_eq!(result.as_os_str(), expected.as_os_str(), "{path:?}");
+    assert_eq!(matches!(result, Cow::Borrowed(_)), matches!(expected, Cow::Borrowed(_)));
+  }
+
+  let slash = to_slash_all(&graph);
+  for (path, result) in graph.iter().zip(&slash) {
+    assert_eq!(*result, path.to_slash(), "{path:?}");
+  }
+}
+
+#[test]
+fn relative_all_matches_relative_for_absolute_and_dirty_bases() {
+  let graph = module_graph();
+  let absolute: Vec<_> = graph.iter().filter(|path| path.is_absolute()).collect();
+
+  #[cfg(target_family = "windows")]
+  let bases = [r"C:\workspace", r"C:\workspace\src\.\", r"C:\workspace\dist\..\node_modules"];
+  #[cfg(not(target_family = "windows"))]
+  let bases = ["/workspace", "/workspace/src/./", "/workspace/dist/../node_modules"];
+
+  for base in bases {
+    let relative = relative_all(&absolute, base);
+    assert_eq!(relative.len(), absolute.len());
+    for (target, result) in absolute.iter().zip(&relative) {
+      let expected = target.relative(base);
+      assert_eq!(result.as_os_str(), expected.as_os_str(), "{target:?} from {base:?}");
+      // The pre-resolved base can only let more results borrow.
+      if matches!(expected, Cow::Borrowed(_)) {
+        assert!(matches!(result, Cow::Borrowed(_)), "{target:?} from {base:?}");
+      }
+    }
+  }
+}
+
+#[test]
+fn racing_batches_resolve_against_one_current_directory() {
+  let cwd = env::current_dir().expect("read the current directory");
+  let base = cwd.join("dist");
+  let graph = module_graph();
+
+  // Relative targets make every worker of every batch ask for the cwd at once,
+  // which races the `cached_current_dir` initialization when it is enabled.
+  // This test and the relative-target test below are the only ones in this
+  // file that read ambient cwd state.
+  let graph = Arc::new(graph);
+  let start = Arc::new(Barrier::new(8));
+  let batches: Vec<_> = (0..8)
+    .map(|_| {
+      let (graph, start, base) = (Arc::clone(&graph), Arc::clone(&start), base.clone());
+      thread::spawn(move || {
+        start.wait();
+        relative_all(&graph, &base).into_iter().map(Cow::into_owned).collect::<Vec<_>>()
+      })
+    })
+    .collect();
+
+  let expected: Vec<_> = graph.iter().map(|target| target.relative(&base).into_owned()).collect();
+  for batch in batches {
+    assert_eq!(batch.join().expect("batch thread panicked"), expected);
+  }
+
+  let relative_base = relative_all(&graph, "dist");
+  assert_eq!(relative_base, expected);
+}
+
+#[test]
+fn relative_all_resolves_relative_targets_against_the_current_directory() {
+  let cwd = env::current_dir().expect("read the current directory");
+  let targets: Vec<PathBuf> = ["src/index.js", "./src/util/../lib.js", "../outside/a.js", "."]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+
+  for base in [cwd.join("src"), PathBuf::from("src"), PathBuf::from("dist/./chunks")] {
+    let relative = relative_all(&targets, &base);
+    for (target, result) in targets.iter().zip(&relative) {
+      assert_eq!(*result, target.relative(&base), "{target:?} from {base:?}");
+      assert_eq!(*result, target.relative_with(&base, &cwd), "{target:?} from {base:?}");
+    }
+  }
+}
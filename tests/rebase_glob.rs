@@ -0,0 +1,95 @@
+use sugar_path::{rebase_glob, rebase_glob_with};
+
+#[test]
+fn rebase_glob_resolves_the_literal_prefix() {
+  assert_eq!(rebase_glob("src/**/*.ts", "packages/app", "."), "packages/app/src/**/*.ts");
+  assert_eq!(rebase_glob("./src/./lib/*.ts", "packages/app", "packages"), "app/src/lib/*.ts");
+  assert_eq!(rebase_glob("../shared/*.css", "packages/app", "."), "packages/shared/*.css");
+  assert_eq!(rebase_glob("src/index.ts", "packages/app", "packages/app/src"), "index.ts");
+  assert_eq!(rebase_glob("*.ts", "packages/app", "packages/lib"), "../app/*.ts");
+  assert_eq!(rebase_glob("**", "packages/app", "packages/app"), "**");
+  assert_eq!(rebase_glob("**/*.rs", ".", "crates/core"), "../../**/*.rs");
+}
+
+#[test]
+fn rebase_glob_keeps_magic_segments_verbatim() {
+  assert_eq!(
+    rebase_glob("src/{a,b}/../[xy]?/**/*.{js,ts}", "app", "."),
+    "app/src/{a,b}/../[xy]?/**/*.{js,ts}"
+  );
+  assert_eq!(rebase_glob("./a/b*/./c", "app", "."), "app/a/b*/./c");
+  assert_eq!(rebase_glob(r"a/\*literal/x", "app", "."), r"app/a/\*literal/x");
+}
+
+#[test]
+fn rebase_glob_keeps_negation_and_trailing_separators() {
+  assert_eq!(rebase_glob("!dist/**", "app", "."), "!app/dist/**");
+  assert_eq!(rebase_glob("!src", "app", "app/src"), "!.");
+  assert_eq!(rebase_glob("dist/", "app", "."), "app/dist/");
+  assert_eq!(rebase_glob("./", "app", "app"), "./");
+  assert_eq!(rebase_glob("", "app", "app"), ".");
+}
+
+#[test]
+fn rebase_glob_escapes_glob_characters_in_directory_names() {
+  assert_eq!(rebase_glob("*.ts", "pkg[1]/{x}", "."), "pkg[[]1[]]/[{]x[}]/*.ts");
+  assert_eq!(rebase_glob("a*/*.ts", "why?", "."), "why[?]/a*/*.ts");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_rebase_glob_escapes_backslashes_in_directory_names() {
+  use sugar_path::glob::Pattern;
+
+  let rebased = rebase_glob("a/*.ts", r"x\y", ".");
+  assert_eq!(rebased, r"x\\y/a/*.ts");
+  let pattern = Pattern::new(&rebased).unwrap();
+  assert!(pattern.matches(r"x\y/a/index.ts"));
+  assert!(!pattern.matches("xy/a/index.ts"));
+
+  assert_eq!(rebase_glob("*", r"[\]", "."), r"[[]\\[]]/*");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_rebase_glob_keeps_absolute_patterns_absolute() {
+  assert_eq!(rebase_glob("/opt/./cache/../data/**", "app", "."), "/opt/data/**");
+  assert_eq!(rebase_glob("/**/*.log", "app", "."), "/**/*.log");
+  assert_eq!(rebase_glob("src/**", "/work/app", "/work"), "app/src/**");
+  assert_eq!(rebase_glob("src/**", "/work/app", "/other"), "../work/app/src/**");
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_rebase_glob_uses_slashes_and_keeps_drive_roots() {
+  assert_eq!(rebase_glob("src/**", r"C:\work\app", r"C:\work"), "app/src/**");
+  assert_eq!(rebase_glob("C:/opt/./cache/**", "app", "."), "C:/opt/cache/**");
+  assert_eq!(rebase_glob("src/**", r"D:\work\app", r"C:\work"), "D:/work/app/src/**");
+}
+
+#[test]
+fn rebase_glob_escapes_a_leading_bang_in_directory_names() {
+  use sugar_path::glob::Pattern;
+
+  let rebased = rebase_glob("*.ts", "!tmp", ".");
+  assert_eq!(rebased, r"\!tmp/*.ts");
+  let pattern = Pattern::new(&rebased).unwrap();
+  assert!(pattern.matches("!tmp/a.ts"));
+  assert!(!pattern.matches("tmp/a.ts"));
+
+  assert_eq!(rebase_glob("!*.ts", "a/!b", "."), r"!a/\!b/*.ts");
+  assert_eq!(rebase_glob("*.ts", "a!b", "."), "a!b/*.ts");
+}
+
+#[test]
+fn rebase_glob_with_resolves_against_the_explicit_cwd() {
+  #[cfg(target_family = "unix")]
+  let (cwd, workspace) = ("/work", "/work/packages");
+  #[cfg(target_family = "windows")]
+  let (cwd, workspace) = (r"C:\work", r"C:\work\packages");
+
+  assert_eq!(rebase_glob_with("src/**", "packages/app", workspace, cwd), "app/src/**");
+  assert_eq!(rebase_glob_with("src/**", workspace, "packages/lib", cwd), "../src/**");
+  assert_eq!(rebase_glob_with("*.ts", "app", "lib", cwd), "../app/*.ts");
+  assert_eq!(rebase_glob("*.ts", "app", "lib"), rebase_glob_with("*.ts", "app", "lib", cwd));
+}
@@ -1,11 +1,14 @@
-#![cfg(all(not(target_family = "windows"), target_arch = "aarch64", target_feature = "neon"))]
+#![cfg(all(
+  not(target_family = "windows"),
+  any(all(target_arch = "aarch64", target_feature = "neon"), target_arch = "x86_64")
+))]
 
 use std::path::Path;
 
 use sugar_path::SugarPath;
 
 #[test]
-fn absolute_relative_paths_cross_neon_block_boundaries_exactly() {
+fn absolute_relative_paths_cross_vector_block_boundaries_exactly() {
   for common_prefix_len in [15, 16, 17, 31, 32, 33, 63, 64, 65, 127, 128, 129, 255, 256] {
     // Both inputs first differ after `/{shared}/`, whose byte length is the
     // requested common-prefix length.
@@ -20,3 +23,27 @@ fn absolute_relative_paths_cross_neon_block_boundaries_exactly() {
     );
   }
 }
+
+#[test]
+fn dot_components_at_vector_block_boundaries_are_normalized() {
+  for separator_position in [14, 15, 16, 17, 30, 31, 32, 33, 63, 64, 65] {
+    // The separator that starts the dot component is the byte at
+    // `separator_position`.
+    let shared = "x".repeat(separator_position - 1);
+    for (dirty, expected) in [
+      (format!("/{shared}/./a.js"), format!("/{shared}/a.js")),
+      (format!("/{shared}//a.js"), format!("/{shared}/a.js")),
+      (format!("/{shared}/y/../a.js"), format!("/{shared}/a.js")),
+      (format!("/{shared}/."), format!("/{shared}")),
+    ] {
+      assert_eq!(
+        Path::new(&dirty).normalize().as_os_str(),
+        Path::new(&expected).as_os_str(),
+        "{dirty}"
+      );
+    }
+
+    let clean = format!("/{shared}/.github/a.js");
+    assert!(matches!(Path::new(&clean).normalize(), std::borrow::Cow::Borrowed(_)), "{clean}");
+  }
+}
@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+use sugar_path::{PortabilityTargets, SanitizeOptions, SugarPath, sanitize_file_name};
+
+fn sanitize(name: &str) -> String {
+  sanitize_file_name(name, &SanitizeOptions::new()).into_owned()
+}
+
+#[test]
+fn separators_and_illegal_characters_are_replaced() {
+  assert_eq!(sanitize("a/b\\c"), "a_b_c");
+  assert_eq!(sanitize("what? <draft> \"v2\" | *"), "what_ _draft_ _v2_ _ _");
+  assert_eq!(sanitize("chunk:main"), "chunk_main");
+  assert_eq!(sanitize("nul\0byte\ttab"), "nul_byte_tab");
+  assert_eq!(sanitize("résumé 📄.txt"), "résumé 📄.txt");
+
+  let posix = SanitizeOptions::new().targets(PortabilityTargets::POSIX);
+  assert_eq!(sanitize_file_name("-résumé v2.txt", &posix), "_r_sum__v2.txt");
+  assert_eq!(sanitize_file_name("CON. ", &posix), "CON._");
+
+  let custom = SanitizeOptions::new().replacement('-');
+  assert_eq!(sanitize_file_name("a:b", &custom), "a-b");
+}
+
+#[test]
+fn reserved_names_are_escaped_and_trailing_dots_trimmed_for_windows() {
+  assert_eq!(sanitize("CON"), "_CON");
+  assert_eq!(sanitize("com1.tar.gz"), "_com1.tar.gz");
+  assert_eq!(sanitize("aux. "), "_aux");
+  assert_eq!(sanitize("console"), "console");
+  assert_eq!(sanitize("notes. . "), "notes");
+  assert_eq!(sanitize(" . "), "_");
+
+  let macos = SanitizeOptions::new().targets(PortabilityTargets::MACOS);
+  assert_eq!(sanitize_file_name("CON.", &macos), "CON.");
+}
+
+#[test]
+fn empty_and_dot_names_become_the_replacement() {
+  for name in ["", ".", "..", "...", "   "] {
+    assert_eq!(sanitize(name), "_", "{name:?}");
+  }
+  let macos = SanitizeOptions::new().targets(PortabilityTargets::MACOS);
+  assert_eq!(sanitize_file_name("..", &macos), "_");
+  assert_eq!(sanitize_file_name("...", &macos), "...");
+}
+
+#[test]
+fn length_is_capped_in_bytes_on_character_boundaries() {
+  let options = SanitizeOptions::new().max_len(5);
+  assert_eq!(sanitize_file_name("abcdefgh", &options), "abcde");
+  assert_eq!(sanitize_file_name("ab€cd", &options), "ab€");
+  assert_eq!(sanitize_file_name("abcd€", &options), "abcd");
+  assert_eq!(sanitize_file_name("abc. xyz", &options), "abc");
+  assert_eq!(sanitize_file_name("COM1.txt", &SanitizeOptions::new().max_len(5)), "_COM1");
+  assert_eq!(sanitize_file_name("€", &SanitizeOptions::new().max_len(0)), "_");
+  assert_eq!(sanitize(&"a".repeat(300)).len(), 255);
+}
+
+#[test]
+fn safe_names_and_truncated_prefixes_borrow() {
+  for (name, expected) in [("index.js", "index.js"), ("name. ", "name"), ("abcdef", "abc")] {
+    let options = SanitizeOptions::new().max_len(if name == "abcdef" { 3 } else { 255 });
+    let sanitized = sanitize_file_name(name, &options);
+    let Cow::Borrowed(sanitized) = sanitized else {
+      panic!("expected {name:?} to borrow");
+    };
+    assert_eq!(sanitized, expected);
+    assert_eq!(sanitized.as_ptr(), name.as_ptr());
+  }
+  assert!(matches!(sanitize_file_name("a:b", &SanitizeOptions::new()), Cow::Owned(_)));
+}
+
+#[test]
+fn sanitized_names_have_no_portability_issues() {
+  let inputs =
+    ["CON", "a<b>", "trailing. ", "-flag", "..", "x/y\\z", "COM²", "tab\t", "lpt1 .log", "résumé"];
+  for name in inputs {
+    let options = SanitizeOptions::new().targets(PortabilityTargets::ALL);
+    let sanitized = sanitize_file_name(name, &options);
+    assert!(
+      sanitized.portability_issues(PortabilityTargets::ALL).is_empty(),
+      "{name:?} -> {sanitized:?}"
+    );
+    assert_eq!(sanitized.as_path().components().count(), 1, "{name:?} -> {sanitized:?}");
+  }
+}
+
+#[test]
+#[should_panic(expected = "cannot be used as a file-name replacement")]
+fn illegal_replacements_are_rejected() {
+  let _ = SanitizeOptions::new().replacement('/');
+}
+
+#[test]
+#[should_panic(expected = "cannot be used as a file-name replacement")]
+fn replacements_are_checked_against_the_configured_targets() {
+  let _ = SanitizeOptions::new().targets(PortabilityTargets::ALL).replacement('-');
+}
+
+#[test]
+#[should_panic(expected = "cannot be used as a file-name replacement")]
+fn targets_are_checked_against_the_configured_replacement() {
+  let _ = SanitizeOptions::new().replacement('-').targets(PortabilityTargets::POSIX);
+}
+
+#[test]
+fn replacements_only_need_to_be_safe_on_the_configured_targets() {
+  let options = SanitizeOptions::new().targets(PortabilityTargets::WINDOWS).replacement('-');
+  assert_eq!(sanitize_file_name("-a:b", &options), "-a-b");
+  assert_eq!(sanitize_file_name("", &options), "-");
+
+  let options = SanitizeOptions::new().targets(PortabilityTargets::MACOS).replacement('*');
+  assert_eq!(sanitize_file_name("a:b", &options), "a*b");
+}
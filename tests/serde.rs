@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sugar_path::SugarPath;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Slash {
+  #[serde(with = "sugar_path::serde::slash")]
+  path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Normalized {
+  #[serde(with = "sugar_path::serde::normalized")]
+  path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct RelativeToCwd {
+  #[serde(with = "sugar_path::serde::relative_to_cwd")]
+  path: PathBuf,
+}
+
+fn native(slash: &str) -> PathBuf {
+  slash.split('/').collect()
+}
+
+#[test]
+fn slash_writes_components_unchanged_with_slashes() {
+  let value = Slash { path: native("src/./lib.rs") };
+  let json = serde_json::to_string(&value).unwrap();
+  assert_eq!(json, r#"{"path":"src/./lib.rs"}"#);
+
+  let read: Slash = serde_json::from_str(&json).unwrap();
+  assert_eq!(read.path.as_os_str(), native("src/./lib.rs").as_os_str());
+}
+
+#[test]
+fn normalized_writes_and_reads_normalized_paths() {
+  let value = Normalized { path: native("a/./b/../c.rs") };
+  let json = serde_json::to_string(&value).unwrap();
+  assert_eq!(json, r#"{"path":"a/c.rs"}"#);
+
+  let read: Normalized = serde_json::from_str(r#"{"path":"x//y/./z/../w"}"#).unwrap();
+  assert_eq!(read.path.as_os_str(), native("x/y/w").as_os_str());
+
+  let read: Normalized = serde_json::from_str(r#"{"path":""}"#).unwrap();
+  assert_eq!(read.path, Path::new("."));
+}
+
+#[test]
+fn relative_to_cwd_round_trips_through_the_process_cwd() {
+  let cwd = std::env::current_dir().unwrap();
+  let value = RelativeToCwd { path: cwd.join("target").join("cache.json") };
+  let json = serde_json::to_string(&value).unwrap();
+  assert_eq!(json, r#"{"path":"target/cache.json"}"#);
+  let read: RelativeToCwd = serde_json::from_str(&json).unwrap();
+  assert_eq!(read, value);
+
+  let json = serde_json::to_string(&RelativeToCwd { path: cwd.clone() }).unwrap();
+  assert_eq!(json, r#"{"path":"."}"#);
+  let read: RelativeToCwd = serde_json::from_str(&json).unwrap();
+  assert_eq!(read.path, cwd);
+
+  let outside = cwd.join("..").join("sibling").normalize().into_owned();
+  let json = serde_json::to_string(&RelativeToCwd { path: outside }).unwrap();
+  assert_eq!(json, r#"{"path":"../sibling"}"#);
+}
+
+#[test]
+fn deserialize_rejects_non_string_input() {
+  assert!(serde_json::from_str::<Slash>(r#"{"path":42}"#).is_err());
+  assert!(serde_json::from_str::<Normalized>(r#"{"path":null}"#).is_err());
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn unix_non_utf8_paths_are_serializer_errors() {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+  let path = PathBuf::from(OsStr::from_bytes(b"bad\xFF/x"));
+  let error = serde_json::to_string(&Slash { path: path.clone() }).unwrap_err();
+  assert!(error.to_string().starts_with("path is not valid Unicode: bad"), "{error}");
+  assert!(serde_json::to_string(&Normalized { path }).is_err());
+}
+
+#[cfg(target_family = "windows")]
+#[test]
+fn windows_paths_are_written_with_slashes_and_read_natively() {
+  let json = serde_json::to_string(&Slash { path: PathBuf::from(r"C:\work\src") }).unwrap();
+  assert_eq!(json, r#"{"path":"C:/work/src"}"#);
+  let read: Slash = serde_json::from_str(&json).unwrap();
+  assert_eq!(read.path.as_os_str(), Path::new(r"C:\work\src").as_os_str());
+}
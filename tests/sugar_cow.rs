@@ -0,0 +1,69 @@
+use std::{
+  borrow::Cow,
+  path::{Path, PathBuf},
+};
+
+use sugar_path::{SugarCow, SugarPath};
+
+fn buffer_identity(path: &Path) -> *const u8 {
+  path.as_os_str().as_encoded_bytes().as_ptr()
+}
+
+#[test]
+fn borrowed_cows_stay_borrowed() {
+  let cwd = std::env::current_dir().unwrap();
+  let target = cwd.join("src").join("lib.rs");
+
+  let Cow::Borrowed(normalized) = Cow::Borrowed(target.as_path()).into_normalized() else {
+    panic!("expected a borrowed normalized path");
+  };
+  assert_eq!(buffer_identity(normalized), buffer_identity(&target));
+
+  let relative = Cow::Borrowed(target.as_path()).into_relative_with(&cwd, &cwd);
+  assert!(matches!(relative, Cow::Borrowed(_)));
+  assert_eq!(relative, Path::new("src").join("lib.rs"));
+
+  assert!(matches!(Cow::Borrowed(Path::new("a")).into_slash(), Cow::Borrowed("a")));
+}
+
+#[test]
+fn owned_cows_reuse_their_buffers() {
+  let cwd = std::env::current_dir().unwrap();
+  let mut target = PathBuf::with_capacity(256);
+  target.push(&cwd);
+  target.push("src");
+  target.push("lib.rs");
+  let identity = buffer_identity(&target);
+
+  let Cow::Owned(relative) = Cow::<Path>::Owned(target).into_relative_with(&cwd, &cwd) else {
+    panic!("expected an owned relative path");
+  };
+  assert_eq!(buffer_identity(&relative), identity);
+
+  let Cow::Owned(normalized) = Cow::<Path>::Owned(relative).into_normalized() else {
+    panic!("expected an owned normalized path");
+  };
+  assert_eq!(buffer_identity(&normalized), identity);
+
+  let Cow::Owned(slash) = Cow::<Path>::Owned(normalized).into_slash() else {
+    panic!("expected an owned slash string");
+  };
+  assert_eq!(slash, "src/lib.rs");
+  assert_eq!(slash.as_ptr(), identity);
+}
+
+#[test]
+fn chained_results_match_borrowed_api() {
+  let cwd = std::env::current_dir().unwrap();
+  let base = cwd.join("a").join("b");
+  for target in [
+    cwd.join("a").join("b").join(".").join("c"),
+    cwd.join("a").join("d").join("..").join("e"),
+    PathBuf::from("x").join("..").join("y"),
+  ] {
+    let expected =
+      target.normalize().relative_with(&base, &cwd).normalize().to_slash().into_owned();
+    let actual = target.normalize().into_relative_with(&base, &cwd).into_normalized().into_slash();
+    assert_eq!(actual, expected, "{target:?}");
+  }
+}
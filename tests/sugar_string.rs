@@ -0,0 +1,79 @@
+use std::path::{MAIN_SEPARATOR_STR, Path};
+
+use sugar_path::{SugarPath, SugarString};
+
+fn native(slash: &str) -> String {
+  slash.split('/').collect::<Vec<_>>().join(MAIN_SEPARATOR_STR)
+}
+
+fn buffer_identity(string: &String) -> (*const u8, usize) {
+  (string.as_ptr(), string.capacity())
+}
+
+#[test]
+fn into_normalized_string_matches_normalize() {
+  for input in ["a/./b/../c", "./a//b/", "../x/../../y", "", ".", "a/..", "src/lib.rs"] {
+    let input = native(input);
+    let expected = input.normalize().into_owned();
+    assert_eq!(Path::new(&input.clone().into_normalized_string()), expected, "{input:?}");
+  }
+}
+
+#[test]
+fn into_normalized_string_reuses_clean_and_current_directory_buffers() {
+  for (input, expected) in [("src/lib.rs", "src/lib.rs"), ("", "."), ("a/..", ".")] {
+    let mut string = String::with_capacity(64);
+    string.push_str(&native(input));
+    let identity = buffer_identity(&string);
+
+    let normalized = string.into_normalized_string();
+    assert_eq!(normalized, native(expected));
+    assert_eq!(buffer_identity(&normalized), identity, "{input:?}");
+  }
+}
+
+#[test]
+fn into_slash_replaces_native_separators_in_place() {
+  let string = native("src/module_loader/mod.rs");
+  let identity = buffer_identity(&string);
+
+  let slash = string.into_slash();
+  assert_eq!(slash, "src/module_loader/mod.rs");
+  assert_eq!(buffer_identity(&slash), identity);
+  assert_eq!(native("a/./b/").into_slash(), "a/./b/");
+}
+
+#[test]
+fn into_relative_string_moves_descendant_suffixes_within_the_receiver() {
+  let cwd = std::env::current_dir().unwrap();
+  let target = cwd.join("src").join("lib.rs");
+  let mut string = String::with_capacity(256);
+  string.push_str(target.to_str().unwrap());
+  let identity = buffer_identity(&string);
+
+  let relative = string.into_relative_string(&cwd);
+  assert_eq!(relative, native("src/lib.rs"));
+  assert_eq!(buffer_identity(&relative), identity);
+}
+
+#[test]
+fn into_relative_string_matches_relative() {
+  let cwd = std::env::current_dir().unwrap();
+  let cases = [
+    (cwd.join("a").join("b"), cwd.join("a").join("c")),
+    (cwd.join("a"), cwd.join("a")),
+    (cwd.join("a").join(".").join("b"), cwd.join("a")),
+    (Path::new("x").join("y"), Path::new("x").to_path_buf()),
+    (Path::new("..").to_path_buf(), Path::new("a").to_path_buf()),
+  ];
+
+  for (target, base) in cases {
+    let expected = target.relative(&base).into_owned();
+    let string = target.to_str().unwrap().to_owned();
+    assert_eq!(
+      Path::new(&string.into_relative_string(&base)),
+      expected,
+      "{target:?} from {base:?}"
+    );
+  }
+}
@@ -0,0 +1,85 @@
+#![cfg(target_family = "windows")]
+
+use std::{borrow::Cow, path::Path};
+
+use sugar_path::SugarPath;
+mod test_utils;
+
+#[test]
+fn to_verbatim_prefixes_normalized_drive_and_unc_paths() {
+  assert_eq_str!(r"C:\x".to_verbatim(), r"\\?\C:\x");
+  assert_eq_str!("c:/a/./b/../x".to_verbatim(), r"\\?\c:\a\x");
+  assert_eq_str!(r"C:\".to_verbatim(), r"\\?\C:\");
+  assert_eq_str!(r"C:\logs\".to_verbatim(), r"\\?\C:\logs\");
+  assert_eq_str!(r"\\srv\sh\x".to_verbatim(), r"\\?\UNC\srv\sh\x");
+  assert_eq_str!("//srv/sh/x/../y".to_verbatim(), r"\\?\UNC\srv\sh\y");
+  assert_eq_str!(r"\\srv\sh".to_verbatim(), r"\\?\UNC\srv\sh\");
+}
+
+#[test]
+fn to_verbatim_keeps_paths_without_a_verbatim_equivalent() {
+  for (input, expected) in [
+    (r"a\b", r"a\b"),
+    (r"C:a\b", r"C:a\b"),
+    (r"\a\b", r"\a\b"),
+    (r"\\.\COM1", r"\\.\COM1"),
+    (r"\\?\C:\x", r"\\?\C:\x"),
+    (r"\\?\UNC\srv\sh\x", r"\\?\UNC\srv\sh\x"),
+    (r"C:\logs\nul", r"C:\logs\nul"),
+    (r"C:\logs\COM1.txt", r"C:\logs\COM1.txt"),
+    (r"C:\logs\name.", r"C:\logs\name."),
+    (r"C:\logs\name ", r"C:\logs\name "),
+  ] {
+    assert_eq_str!(input.to_verbatim(), expected, "input {input:?}");
+  }
+}
+
+#[test]
+fn strip_verbatim_removes_prefixes_that_do_not_change_meaning() {
+  assert_eq_str!(r"\\?\C:\x".strip_verbatim(), r"C:\x");
+  assert_eq_str!(r"\\?\c:\".strip_verbatim(), r"c:\");
+  assert_eq_str!(r"\\?\UNC\srv\sh\x".strip_verbatim(), r"\\srv\sh\x");
+  assert_eq_str!(r"\\?\UNC\srv\sh\".strip_verbatim(), r"\\srv\sh\");
+  assert_eq_str!(r"\\?\C:\console\nullable\com10".strip_verbatim(), r"C:\console\nullable\com10");
+
+  let input = r"\\?\C:\workspace\src";
+  let Cow::Borrowed(stripped) = input.strip_verbatim() else {
+    panic!("expected a stripped drive path to borrow");
+  };
+  assert_eq!(stripped.as_os_str().as_encoded_bytes().as_ptr(), input[4..].as_ptr());
+}
+
+#[test]
+fn strip_verbatim_falls_back_when_the_prefix_is_load_bearing() {
+  for input in [
+    r"\\?\C:\a/b",
+    r"\\?\C:\a\.\b",
+    r"\\?\C:\a\..\b",
+    r"\\?\C:\a.",
+    r"\\?\C:\a ",
+    r"\\?\C:\CON",
+    r"\\?\C:\dir\aux.txt",
+    r"\\?\C:\dir\lpt1 .log",
+    r"\\?\C:\dir\COM²",
+    r"\\?\C:\dir\conin$",
+    r"\\?\C:",
+    r"\\?\UNC\srv/x\sh\a",
+    r"\\?\Volume{00000000-0000-0000-0000-000000000000}\x",
+    r"\\?\GLOBALROOT\Device\x",
+    r"C:\x",
+    r"\\srv\sh\x",
+    r"relative\x",
+  ] {
+    let stripped = input.strip_verbatim();
+    assert!(matches!(stripped, Cow::Borrowed(_)), "input {input:?}");
+    assert_eq!(stripped.as_os_str(), Path::new(input).as_os_str(), "input {input:?}");
+  }
+}
+
+#[test]
+fn verbatim_round_trips_for_stable_paths() {
+  for input in [r"C:\workspace\src\lib.rs", r"\\srv\sh\dir\file.txt", r"D:\"] {
+    let verbatim = input.to_verbatim();
+    assert_eq!(verbatim.strip_verbatim().as_os_str(), Path::new(input).as_os_str());
+  }
+}
@@ -0,0 +1,112 @@
+use std::{
+  ffi::OsStr,
+  path::{Path, PathBuf},
+};
+
+use sugar_path::SugarPath;
+
+fn buffer_identity(path: &PathBuf) -> (*const u8, usize) {
+  (path.as_os_str().as_encoded_bytes().as_ptr(), path.capacity())
+}
+
+#[test]
+fn normalize_into_matches_normalize() {
+  #[cfg(target_family = "unix")]
+  let cases = ["", ".", "./", "a/../..", "a/./b/", "/a/../../b", "//a//b", "../x/../y"];
+  #[cfg(target_family = "windows")]
+  let cases = ["", ".", r".\", r"a\..\..", r"a\.\b\", r"C:\a\..\..\b", r"c:a\..", r"\\?\C:\a\.\b"];
+
+  let mut out = PathBuf::from("stale/contents/that/must/be/replaced");
+  for input in cases {
+    input.normalize_into(&mut out);
+    assert_eq!(out.as_os_str(), input.normalize().as_os_str(), "{input:?}");
+
+    OsStr::new(input).normalize_into(&mut out);
+    assert_eq!(out.as_os_str(), input.normalize().as_os_str(), "{input:?}");
+  }
+}
+
+#[test]
+fn relative_into_matches_relative() {
+  #[cfg(target_family = "unix")]
+  let cases = [
+    ("/workspace/src/lib.rs", "/workspace"),
+    ("/workspace", "/workspace/src/lib.rs"),
+    ("/workspace", "/workspace"),
+    ("/other/dist", "/workspace/src"),
+  ];
+  #[cfg(target_family = "windows")]
+  let cases = [
+    (r"C:\workspace\src\lib.rs", r"C:\workspace"),
+    (r"C:\workspace", r"C:\workspace\src\lib.rs"),
+    (r"C:\workspace", r"C:\workspace"),
+    (r"D:\other\dist", r"C:\workspace\src"),
+  ];
+
+  let mut out = PathBuf::from("stale/contents/that/must/be/replaced");
+  for (target, base) in cases {
+    Path::new(target).relative_into(base, &mut out);
+    assert_eq!(out, Path::new(target).relative(base), "{target:?} from {base:?}");
+
+    target.relative_into(base, &mut out);
+    assert_eq!(out, Path::new(target).relative(base), "{target:?} from {base:?}");
+  }
+}
+
+#[test]
+fn to_slash_into_matches_to_slash() {
+  let native = PathBuf::from("src").join("module_loader").join("module_task.rs");
+  let mut out = String::from("stale contents that must be replaced");
+
+  native.to_slash_into(&mut out);
+  assert_eq!(out, native.to_slash());
+
+  native.as_os_str().to_slash_into(&mut out);
+  assert_eq!(out, native.to_slash());
+
+  "src/lib.rs".to_slash_into(&mut out);
+  assert_eq!(out, "src/lib.rs");
+}
+
+#[test]
+fn write_into_methods_reuse_grown_buffers() {
+  #[cfg(target_family = "unix")]
+  let (dirty, target, base) =
+    ("/workspace/./src/../src/lib.rs", "/workspace/src/lib.rs", "/workspace");
+  #[cfg(target_family = "windows")]
+  let (dirty, target, base) =
+    (r"C:\workspace\.\src\..\src\lib.rs", r"C:\workspace\src\lib.rs", r"C:\workspace");
+  #[cfg(target_family = "unix")]
+  let (sibling, sibling_base) = ("/workspace/crates/a/x.rs", "/workspace/crates/b");
+  #[cfg(target_family = "windows")]
+  let (sibling, sibling_base) = (r"C:\workspace\crates\a\x.rs", r"C:\workspace\crates\b");
+
+  let mut out = PathBuf::with_capacity(256);
+  let identity = buffer_identity(&out);
+  for _ in 0..3 {
+    dirty.normalize_into(&mut out);
+    assert_eq!(buffer_identity(&out), identity);
+    target.relative_into(base, &mut out);
+    assert_eq!(buffer_identity(&out), identity);
+    sibling.relative_into(sibling_base, &mut out);
+    assert_eq!(out, Path::new("..").join("a").join("x.rs"));
+    assert_eq!(buffer_identity(&out), identity);
+  }
+
+  let mut text = String::with_capacity(256);
+  let identity = (text.as_ptr(), text.capacity());
+  for _ in 0..3 {
+    Path::new(target).to_slash_into(&mut text);
+    assert_eq!((text.as_ptr(), text.capacity()), identity);
+  }
+}
+
+#[test]
+#[should_panic(expected = "path is not valid Unicode")]
+#[cfg(target_family = "unix")]
+fn to_slash_into_panics_on_invalid_unicode() {
+  use std::os::unix::ffi::OsStrExt;
+
+  let mut out = String::new();
+  OsStr::from_bytes(b"src/\xFF.rs").to_slash_into(&mut out);
+}